@@ -0,0 +1,418 @@
+//! `#[derive(DevcordEvent)]`, implementing `TypedEvent`, `TopicEvent` and `KeyEvent` for an
+//! event enum, and `TopicEvent` for its event type enum, from `#[event(...)]` attributes.
+//!
+//! ```ignore
+//! #[derive(Serialize, Deserialize, DevcordEvent)]
+//! #[serde(tag = "kind")]
+//! #[event(event_type = EventType::Auth, types = AuthEventType)]
+//! pub enum AuthEvent {
+//!     #[serde(rename = "user_signed_up")]
+//!     #[event(type = SignedUp, topic = "auth-signed", key = id)]
+//!     UserSignedUpEvent(UserCreated),
+//! }
+//! ```
+//!
+//! Every variant wraps one payload and needs a `type` and a `topic`. Its `key` is a field of
+//! the payload, records are published without a key when it is left out. Variants sharing a
+//! `type` must share a `topic`, and every variant of the event type enum needs a variant using it.
+//!
+//! `config = auth_signed_config` names a `fn() -> TopicConfig` the topic of the variant's
+//! `type` is created with, instead of the default one. Variants sharing a `type` may leave it
+//! out, but must not name another one.
+//!
+//! Adding `samples` to the attribute of the enum also generates `pub(crate) fn samples()`,
+//! one event of each variant with a default payload, so every payload must implement `Default`.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    Data, DeriveInput, Error, Fields, Ident, LitStr, Path, Result, Type, parse_macro_input,
+    spanned::Spanned,
+};
+
+#[proc_macro_derive(DevcordEvent, attributes(event))]
+pub fn derive_devcord_event(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// `#[event(event_type = EventType::Auth, types = AuthEventType, samples)]` on the enum.
+struct Container {
+    /// Constructor of the top-level event type wrapping the types of this enum.
+    event_type: Path,
+    types: Path,
+    samples: bool,
+}
+
+/// `#[event(type = SignedUp, topic = "auth-signed", key = id, config = signed_config)]` on a
+/// variant.
+struct Variant {
+    ident: Ident,
+    payload: Type,
+    event_type: Ident,
+    topic: LitStr,
+    key: Option<Ident>,
+    config: Option<Path>,
+}
+
+fn expand(input: DeriveInput) -> Result<TokenStream2> {
+    let Data::Enum(data) = &input.data else {
+        return Err(Error::new(
+            input.span(),
+            "DevcordEvent can only be derived for enums",
+        ));
+    };
+    let container = parse_container(&input)?;
+    let variants = data
+        .variants
+        .iter()
+        .map(parse_variant)
+        .collect::<Result<Vec<_>>>()?;
+    check_topics(&variants)?;
+    check_configs(&variants)?;
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let Container {
+        event_type,
+        types,
+        samples,
+    } = &container;
+    let mut wrapper = event_type.clone();
+    wrapper.segments.pop();
+    wrapper.segments.pop_punct();
+
+    let krate = quote!(::devcord_events);
+    let idents = variants
+        .iter()
+        .map(|variant| &variant.ident)
+        .collect::<Vec<_>>();
+    let payloads = variants.iter().map(|variant| &variant.payload);
+    let event_types = variants.iter().map(|variant| &variant.event_type);
+    let topics = variants.iter().map(|variant| &variant.topic);
+    let keys = variants.iter().map(|variant| match &variant.key {
+        Some(key) => quote!(#krate::publisher::topic::fluvio::RecordKey::from(
+            ::std::string::ToString::to_string(&event.#key)
+        )),
+        None => quote!(#krate::publisher::topic::fluvio::RecordKey::NULL),
+    });
+
+    let variant_types = variants.iter().map(|variant| &variant.event_type);
+
+    let mut type_topics = Vec::<(&Ident, &LitStr)>::new();
+    for variant in &variants {
+        if !type_topics
+            .iter()
+            .any(|(known, _)| *known == &variant.event_type)
+        {
+            type_topics.push((&variant.event_type, &variant.topic));
+        }
+    }
+    let (type_idents, type_topics): (Vec<_>, Vec<_>) = type_topics.into_iter().unzip();
+    let type_configs = type_idents.iter().map(|event_type| {
+        let config = variants
+            .iter()
+            .filter(|variant| variant.event_type == **event_type)
+            .find_map(|variant| variant.config.as_ref());
+        match config {
+            Some(config) => quote!(#config()),
+            None => quote!(::std::default::Default::default()),
+        }
+    });
+
+    let samples = samples.then(|| {
+        quote! {
+            impl #impl_generics #name #ty_generics #where_clause {
+                /// One event of each variant, with default payloads.
+                pub(crate) fn samples() -> ::std::vec::Vec<Self> {
+                    ::std::vec![#( Self::#idents(<#payloads as ::std::default::Default>::default()) ),*]
+                }
+            }
+        }
+    });
+
+    Ok(quote! {
+        #samples
+
+        impl #impl_generics #krate::publisher::TypedEvent for #name #ty_generics #where_clause {
+            type EventType = #wrapper;
+
+            fn event_type(&self) -> Self::EventType {
+                #event_type(match self {
+                    #( Self::#idents(_) => #types::#event_types, )*
+                })
+            }
+        }
+
+        impl #impl_generics #krate::publisher::topic::TopicEvent for #name #ty_generics #where_clause {
+            fn event_topic(&self) -> #krate::publisher::topic::Topic {
+                match self {
+                    #( Self::#idents(_) => #topics, )*
+                }
+            }
+
+            fn topic_config(&self) -> #krate::publisher::topic::config::TopicConfig {
+                match self {
+                    #( Self::#idents(_) => #krate::publisher::topic::TopicEvent::topic_config(&#types::#variant_types), )*
+                }
+            }
+        }
+
+        impl #impl_generics #krate::publisher::topic::fluvio::KeyEvent for #name #ty_generics #where_clause {
+            #[allow(unused_variables)]
+            fn event_key(&self) -> #krate::publisher::topic::fluvio::RecordKey {
+                match self {
+                    #( Self::#idents(event) => #keys, )*
+                }
+            }
+        }
+
+        impl #krate::publisher::topic::TopicEvent for #types {
+            fn event_topic(&self) -> #krate::publisher::topic::Topic {
+                match self {
+                    #( #types::#type_idents => #type_topics, )*
+                }
+            }
+
+            fn topic_config(&self) -> #krate::publisher::topic::config::TopicConfig {
+                match self {
+                    #( #types::#type_idents => #type_configs, )*
+                }
+            }
+        }
+    })
+}
+
+fn parse_container(input: &DeriveInput) -> Result<Container> {
+    let mut event_type = None;
+    let mut types = None;
+    let mut samples = false;
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("event"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("event_type") {
+                event_type = Some(meta.value()?.parse::<Path>()?);
+            } else if meta.path.is_ident("types") {
+                types = Some(meta.value()?.parse::<Path>()?);
+            } else if meta.path.is_ident("samples") {
+                samples = true;
+            } else {
+                return Err(meta.error("expected `event_type`, `types` or `samples`"));
+            }
+            Ok(())
+        })?;
+    }
+
+    let missing = |name| {
+        Error::new(
+            input.ident.span(),
+            format!("missing `{name}` in #[event(event_type = ..., types = ...)]"),
+        )
+    };
+    let event_type = event_type.ok_or_else(|| missing("event_type"))?;
+    if event_type.segments.len() < 2 {
+        return Err(Error::new(
+            event_type.span(),
+            "`event_type` must name a variant, such as `EventType::Auth`",
+        ));
+    }
+    Ok(Container {
+        event_type,
+        types: types.ok_or_else(|| missing("types"))?,
+        samples,
+    })
+}
+
+fn parse_variant(variant: &syn::Variant) -> Result<Variant> {
+    let payload = match &variant.fields {
+        Fields::Unnamed(fields) if fields.unnamed.len() == 1 => fields.unnamed[0].ty.clone(),
+        _ => {
+            return Err(Error::new(
+                variant.span(),
+                "DevcordEvent variants must wrap exactly one payload",
+            ));
+        }
+    };
+
+    let mut event_type = None;
+    let mut topic = None;
+    let mut key = None;
+    let mut config = None;
+    for attr in variant
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("event"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("type") {
+                event_type = Some(meta.value()?.parse::<Ident>()?);
+            } else if meta.path.is_ident("topic") {
+                topic = Some(meta.value()?.parse::<LitStr>()?);
+            } else if meta.path.is_ident("key") {
+                key = Some(meta.value()?.parse::<Ident>()?);
+            } else if meta.path.is_ident("config") {
+                config = Some(meta.value()?.parse::<Path>()?);
+            } else {
+                return Err(meta.error("expected `type`, `topic`, `key` or `config`"));
+            }
+            Ok(())
+        })?;
+    }
+
+    let missing = |name| {
+        Error::new(
+            variant.ident.span(),
+            format!(
+                "missing `{name}` in #[event(type = ..., topic = ...)] on variant `{}`",
+                variant.ident
+            ),
+        )
+    };
+    Ok(Variant {
+        ident: variant.ident.clone(),
+        payload,
+        event_type: event_type.ok_or_else(|| missing("type"))?,
+        topic: topic.ok_or_else(|| missing("topic"))?,
+        key,
+        config,
+    })
+}
+
+/// Events of one type all go to the topic of that type.
+fn check_topics(variants: &[Variant]) -> Result<()> {
+    for (i, variant) in variants.iter().enumerate() {
+        let conflict = variants[..i].iter().find(|other| {
+            other.event_type == variant.event_type && other.topic.value() != variant.topic.value()
+        });
+        if let Some(other) = conflict {
+            return Err(Error::new(
+                variant.topic.span(),
+                format!(
+                    "type `{}` is already published to \"{}\" by variant `{}`",
+                    variant.event_type,
+                    other.topic.value(),
+                    other.ident
+                ),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// The topic of a type is created with one config, which any of its variants may name.
+fn check_configs(variants: &[Variant]) -> Result<()> {
+    let tokens = |config: &Path| quote!(#config).to_string();
+    for (i, variant) in variants.iter().enumerate() {
+        let Some(config) = &variant.config else {
+            continue;
+        };
+        let conflict = variants[..i].iter().find(|other| {
+            other.event_type == variant.event_type
+                && other
+                    .config
+                    .as_ref()
+                    .is_some_and(|other| tokens(other) != tokens(config))
+        });
+        if let Some(other) = conflict {
+            return Err(Error::new(
+                config.span(),
+                format!(
+                    "type `{}` is already configured by variant `{}`",
+                    variant.event_type, other.ident
+                ),
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error(input: TokenStream2) -> String {
+        let input = syn::parse2::<DeriveInput>(input).unwrap();
+        expand(input).err().unwrap().to_string()
+    }
+
+    #[test]
+    fn missing_topic() {
+        let message = error(quote! {
+            #[event(event_type = EventType::Auth, types = AuthEventType)]
+            enum AuthEvent {
+                #[event(type = SignedUp)]
+                UserSignedUpEvent(UserCreated),
+            }
+        });
+        assert!(message.contains("missing `topic`"));
+    }
+
+    #[test]
+    fn missing_container() {
+        let message = error(quote! {
+            enum AuthEvent {
+                #[event(type = SignedUp, topic = "auth-signed")]
+                UserSignedUpEvent(UserCreated),
+            }
+        });
+        assert!(message.contains("missing `event_type`"));
+    }
+
+    #[test]
+    fn samples_are_opt_in() {
+        let expanded = |input: TokenStream2| {
+            let input = syn::parse2::<DeriveInput>(input).unwrap();
+            expand(input).unwrap().to_string()
+        };
+        let plain = expanded(quote! {
+            #[event(event_type = EventType::Auth, types = AuthEventType)]
+            enum AuthEvent {
+                #[event(type = SignedUp, topic = "auth-signed")]
+                UserSignedUpEvent(UserCreated),
+            }
+        });
+        assert!(!plain.contains("fn samples"));
+        let sampled = expanded(quote! {
+            #[event(event_type = EventType::Auth, types = AuthEventType, samples)]
+            enum AuthEvent {
+                #[event(type = SignedUp, topic = "auth-signed")]
+                UserSignedUpEvent(UserCreated),
+            }
+        });
+        assert!(sampled.contains("fn samples"));
+    }
+
+    #[test]
+    fn conflicting_topics() {
+        let message = error(quote! {
+            #[event(event_type = EventType::Auth, types = AuthEventType)]
+            enum AuthEvent {
+                #[event(type = Logged, topic = "auth-logged")]
+                UserLoggedInEvent(UserLoggedIn),
+                #[event(type = Logged, topic = "auth-logged-out")]
+                UserLoggedOutEvent(UserLoggedOut),
+            }
+        });
+        assert!(message.contains("already published"));
+    }
+
+    #[test]
+    fn conflicting_configs() {
+        let message = error(quote! {
+            #[event(event_type = EventType::Auth, types = AuthEventType)]
+            enum AuthEvent {
+                #[event(type = Logged, topic = "auth-logged", config = logged_config)]
+                UserLoggedInEvent(UserLoggedIn),
+                #[event(type = Logged, topic = "auth-logged", config = other_config)]
+                UserLoggedOutEvent(UserLoggedOut),
+            }
+        });
+        assert!(message.contains("already configured"));
+    }
+}
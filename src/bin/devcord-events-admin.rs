@@ -0,0 +1,171 @@
+use std::process::ExitCode;
+
+use devcord_events::{
+    events::EventType,
+    publisher::topic::{
+        Topic, TopicEvent,
+        config::TopicConfig,
+        fluvio::admin::{TopicAdmin, TopicStatus},
+    },
+};
+
+const USAGE: &str = "\
+Usage: devcord-events-admin [--namespace <namespace>] <command>
+
+Commands:
+  list                          Lists every topic events can be published to
+  check                         Reports missing topics and configuration drift
+  provision [--delete-unknown]  Creates missing topics, deleting the unknown ones of the
+                                namespace if asked to";
+
+enum Command {
+    List,
+    Check,
+    Provision { delete_unknown: bool },
+}
+
+struct Args {
+    namespace: Option<String>,
+    command: Command,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let mut namespace = None;
+    let mut command = None;
+    let mut delete_unknown = false;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--namespace" => {
+                namespace = Some(args.next().ok_or("--namespace needs a value")?);
+            }
+            "--delete-unknown" => delete_unknown = true,
+            "list" | "check" | "provision" if command.is_none() => command = Some(arg),
+            _ => return Err(format!("unexpected argument: {arg}")),
+        }
+    }
+
+    let command = match command.as_deref() {
+        Some("list") => Command::List,
+        Some("check") => Command::Check,
+        Some("provision") => Command::Provision { delete_unknown },
+        _ => return Err(String::from("missing command")),
+    };
+    if delete_unknown && !matches!(command, Command::Provision { .. }) {
+        return Err(String::from("--delete-unknown only applies to provision"));
+    }
+    // Without a namespace, every topic of the cluster that is not ours would be deleted.
+    if delete_unknown && namespace.is_none() {
+        return Err(String::from("--delete-unknown needs --namespace"));
+    }
+    Ok(Args { namespace, command })
+}
+
+/// Every topic events can be published to, with the configuration it is created with.
+fn topics() -> Vec<(Topic, TopicConfig)> {
+    let mut topics = Vec::<(Topic, TopicConfig)>::new();
+    for event_type in EventType::all() {
+        let topic = event_type.event_topic();
+        if !topics.iter().any(|(known, _)| *known == topic) {
+            topics.push((topic, event_type.topic_config()));
+        }
+    }
+    topics
+}
+
+async fn run(args: Args) -> anyhow::Result<bool> {
+    let topics = topics();
+    if let Command::List = args.command {
+        for (topic, config) in &topics {
+            println!("{topic}\t{config:?}");
+        }
+        return Ok(true);
+    }
+
+    let admin = TopicAdmin::connect(args.namespace).await?;
+    let mut clean = true;
+    for (name, status) in admin.status(&topics).await? {
+        match (&args.command, status) {
+            (_, TopicStatus::UpToDate) => println!("{name}: up to date"),
+            (_, TopicStatus::Drifted(mismatches)) => {
+                clean = false;
+                println!("{name}: drifted, {}", mismatches.join(", "));
+            }
+            (Command::Provision { .. }, TopicStatus::Missing) => {
+                let (topic, config) = topics
+                    .iter()
+                    .find(|(topic, _)| admin.topic_name(topic) == name)
+                    .expect("missing topics are known topics");
+                admin.create(topic, config).await?;
+                println!("{name}: created");
+            }
+            (
+                Command::Provision {
+                    delete_unknown: true,
+                },
+                TopicStatus::Unknown,
+            ) => {
+                admin.delete(&name).await?;
+                println!("{name}: deleted");
+            }
+            (_, TopicStatus::Missing) => {
+                clean = false;
+                println!("{name}: missing");
+            }
+            (_, TopicStatus::Unknown) => println!("{name}: unknown"),
+        }
+    }
+    Ok(clean)
+}
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> ExitCode {
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{e}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+
+    match run(args).await {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Args, String> {
+        parse_args(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn delete_unknown_needs_a_namespace() {
+        let error = parse(&["provision", "--delete-unknown"]).err().unwrap();
+        assert!(error.contains("--namespace"));
+
+        let args = parse(&["--namespace", "staging", "provision", "--delete-unknown"]).unwrap();
+        assert_eq!(Some(String::from("staging")), args.namespace);
+        assert!(matches!(
+            args.command,
+            Command::Provision {
+                delete_unknown: true
+            }
+        ));
+    }
+
+    #[test]
+    fn delete_unknown_only_applies_to_provision() {
+        let error = parse(&["--namespace", "staging", "check", "--delete-unknown"])
+            .err()
+            .unwrap();
+        assert!(error.contains("only applies to provision"));
+    }
+}
@@ -1,12 +1,8 @@
-use fluvio::RecordKey;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    events::EventType,
-    publisher::{
-        TypedEvent,
-        topic::{TopicEvent, fluvio::KeyEvent},
-    },
+    DevcordEvent,
+    events::{EventType, catalogue::event_type_enum},
 };
 
 #[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
@@ -28,56 +24,25 @@ pub struct UserLoggedOut {
     pub logout_time: i64,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, DevcordEvent)]
 #[serde(tag = "kind")]
+#[event(event_type = EventType::Auth, types = AuthEventType, samples)]
 pub enum AuthEvent {
     #[serde(rename = "user_signed_up")]
+    #[event(type = SignedUp, topic = "auth-signed", key = id)]
     UserSignedUpEvent(UserCreated),
     #[serde(rename = "user_logged_in")]
+    #[event(type = Logged, topic = "auth-logged", key = id)]
     UserLoggedInEvent(UserLoggedIn),
     #[serde(rename = "user_logged_out")]
+    #[event(type = Logged, topic = "auth-logged", key = id)]
     UserLoggedOutEvent(UserLoggedOut),
 }
 
-#[derive(Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
-pub enum AuthEventType {
-    SignedUp,
-    Logged,
-}
-
-impl TopicEvent for AuthEventType {
-    fn event_topic(&self) -> crate::publisher::topic::Topic {
-        match self {
-            AuthEventType::SignedUp => "auth-signed",
-            AuthEventType::Logged => "auth-logged",
-        }
-    }
-}
-
-impl TopicEvent for AuthEvent {
-    fn event_topic(&self) -> crate::publisher::topic::Topic {
-        self.event_type().event_topic()
-    }
-}
-
-impl KeyEvent for AuthEvent {
-    fn event_key(&self) -> fluvio::RecordKey {
-        match self {
-            AuthEvent::UserSignedUpEvent(_) => RecordKey::NULL,
-            AuthEvent::UserLoggedInEvent(_) => RecordKey::NULL,
-            AuthEvent::UserLoggedOutEvent(_) => RecordKey::NULL,
-        }
-    }
-}
-
-impl TypedEvent for AuthEvent {
-    type EventType = EventType;
-
-    fn event_type(&self) -> Self::EventType {
-        EventType::Auth(match self {
-            AuthEvent::UserSignedUpEvent(_) => AuthEventType::SignedUp,
-            AuthEvent::UserLoggedInEvent(_) => AuthEventType::Logged,
-            AuthEvent::UserLoggedOutEvent(_) => AuthEventType::Logged,
-        })
+event_type_enum! {
+    #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+    pub enum AuthEventType {
+        SignedUp,
+        Logged,
     }
 }
@@ -0,0 +1,92 @@
+use serde_json::Value;
+
+use crate::{
+    events::{
+        Event, EventType,
+        auth::{AuthEvent, AuthEventType},
+        group::{GroupEvent, GroupEventType},
+        message::{MessageEvent, MessageEventType},
+        user::{UserEvent, UserEventType},
+    },
+    publisher::{
+        TypedEvent,
+        topic::{Topic, TopicEvent},
+    },
+};
+
+/// Declares a fieldless event type enum along with `ALL`, every one of its variants.
+macro_rules! event_type_enum {
+    (
+        $(#[$meta:meta])*
+        $vis:vis enum $name:ident {
+            $( $variant:ident ),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis enum $name {
+            $( $variant ),*
+        }
+
+        impl $name {
+            pub const ALL: &'static [Self] = &[$( Self::$variant ),*];
+        }
+    };
+}
+
+pub(crate) use event_type_enum;
+
+/// One kind of event `Event` can serialize to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventKind {
+    /// The `service` tag of the event.
+    pub service: String,
+    /// The `kind` tag of the event.
+    pub kind: String,
+    pub event_type: EventType,
+    pub topic: Topic,
+}
+
+impl EventType {
+    /// Every event type, and so every topic events can be published to.
+    pub fn all() -> Vec<EventType> {
+        let user = UserEventType::ALL.iter().copied().map(EventType::User);
+        let auth = AuthEventType::ALL.iter().copied().map(EventType::Auth);
+        let message = MessageEventType::ALL
+            .iter()
+            .copied()
+            .map(EventType::Message);
+        let group = GroupEventType::ALL.iter().copied().map(EventType::Group);
+        user.chain(auth).chain(message).chain(group).collect()
+    }
+}
+
+impl Event {
+    /// One event of each kind, with default payloads.
+    pub(crate) fn samples() -> Vec<Event> {
+        let user = UserEvent::samples().into_iter().map(Event::UserEvent);
+        let auth = AuthEvent::samples().into_iter().map(Event::AuthEvent);
+        let message = MessageEvent::samples().into_iter().map(Event::MessageEvent);
+        let group = GroupEvent::samples().into_iter().map(Event::GroupEvent);
+        user.chain(auth).chain(message).chain(group).collect()
+    }
+
+    /// Every kind of event, with the tags serde writes for it.
+    pub fn kinds() -> Vec<EventKind> {
+        Event::samples()
+            .into_iter()
+            .map(|event| {
+                let value = serde_json::to_value(&event).expect("events serialize to JSON");
+                let tag = |name: &str| match &value[name] {
+                    Value::String(tag) => tag.clone(),
+                    _ => panic!("events are tagged with their {name}"),
+                };
+                EventKind {
+                    service: tag("service"),
+                    kind: tag("kind"),
+                    event_type: event.event_type(),
+                    topic: event.event_topic(),
+                }
+            })
+            .collect()
+    }
+}
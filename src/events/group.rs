@@ -1,12 +1,8 @@
-use fluvio::RecordKey;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    events::EventType,
-    publisher::{
-        TypedEvent,
-        topic::{TopicEvent, fluvio::KeyEvent},
-    },
+    DevcordEvent,
+    events::{EventType, catalogue::event_type_enum},
 };
 
 #[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
@@ -36,60 +32,28 @@ pub struct GroupUserRemovedEvent {
     pub user_id: String,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, DevcordEvent)]
 #[serde(tag = "kind")]
+#[event(event_type = EventType::Group, types = GroupEventType, samples)]
 pub enum GroupEvent {
     #[serde(rename = "group_created")]
+    #[event(type = Groups, topic = "group-groups", key = group_id)]
     GroupCreatedEvent(GroupCreatedEvent),
     #[serde(rename = "group_deleted")]
+    #[event(type = Groups, topic = "group-groups", key = group_id)]
     GroupDeletedEvent(GroupDeletedEvent),
     #[serde(rename = "group_user_added")]
+    #[event(type = Members, topic = "group-members", key = group_id)]
     GroupUserAddedEvent(GroupUserAddedEvent),
     #[serde(rename = "group_user_removed")]
+    #[event(type = Members, topic = "group-members", key = group_id)]
     GroupUserRemovedEvent(GroupUserRemovedEvent),
 }
 
-#[derive(Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
-pub enum GroupEventType {
-    Groups,
-    Members,
-}
-
-impl TopicEvent for GroupEvent {
-    fn event_topic(&self) -> crate::publisher::topic::Topic {
-        self.event_type().event_topic()
-    }
-}
-
-impl TopicEvent for GroupEventType {
-    fn event_topic(&self) -> crate::publisher::topic::Topic {
-        match self {
-            GroupEventType::Groups => "group-groups",
-            GroupEventType::Members => "group-members",
-        }
-    }
-}
-
-impl KeyEvent for GroupEvent {
-    fn event_key(&self) -> fluvio::RecordKey {
-        match self {
-            GroupEvent::GroupCreatedEvent(_) => RecordKey::NULL,
-            GroupEvent::GroupDeletedEvent(_) => RecordKey::NULL,
-            GroupEvent::GroupUserAddedEvent(_) => RecordKey::NULL,
-            GroupEvent::GroupUserRemovedEvent(_) => RecordKey::NULL,
-        }
-    }
-}
-
-impl TypedEvent for GroupEvent {
-    type EventType = EventType;
-
-    fn event_type(&self) -> Self::EventType {
-        EventType::Group(match self {
-            GroupEvent::GroupCreatedEvent(_) => GroupEventType::Groups,
-            GroupEvent::GroupDeletedEvent(_) => GroupEventType::Groups,
-            GroupEvent::GroupUserAddedEvent(_) => GroupEventType::Members,
-            GroupEvent::GroupUserRemovedEvent(_) => GroupEventType::Members,
-        })
+event_type_enum! {
+    #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+    pub enum GroupEventType {
+        Groups,
+        Members,
     }
 }
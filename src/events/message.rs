@@ -1,12 +1,8 @@
-use fluvio::RecordKey;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    events::EventType,
-    publisher::{
-        TypedEvent,
-        topic::{TopicEvent, fluvio::KeyEvent},
-    },
+    DevcordEvent,
+    events::{EventType, catalogue::event_type_enum},
 };
 
 #[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
@@ -16,46 +12,18 @@ pub struct MessageSent {
     pub message: String,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, DevcordEvent)]
 #[serde(tag = "kind")]
+#[event(event_type = EventType::Message, types = MessageEventType, samples)]
 pub enum MessageEvent {
     #[serde(rename = "message_sent")]
+    #[event(type = Sent, topic = "message-sent", key = channel_id)]
     MessageSentEvent(MessageSent),
 }
 
-#[derive(Clone, Copy, Hash, PartialEq, PartialOrd, Ord, Eq)]
-pub enum MessageEventType {
-    Sent,
-}
-
-impl TopicEvent for MessageEvent {
-    fn event_topic(&self) -> crate::publisher::topic::Topic {
-        self.event_type().event_topic()
-    }
-}
-
-impl TopicEvent for MessageEventType {
-    fn event_topic(&self) -> crate::publisher::topic::Topic {
-        match self {
-            MessageEventType::Sent => "message-sent",
-        }
-    }
-}
-
-impl KeyEvent for MessageEvent {
-    fn event_key(&self) -> fluvio::RecordKey {
-        match self {
-            MessageEvent::MessageSentEvent(_) => RecordKey::NULL,
-        }
-    }
-}
-
-impl TypedEvent for MessageEvent {
-    type EventType = EventType;
-
-    fn event_type(&self) -> Self::EventType {
-        EventType::Message(match self {
-            MessageEvent::MessageSentEvent(_) => MessageEventType::Sent,
-        })
+event_type_enum! {
+    #[derive(Debug, Clone, Copy, Hash, PartialEq, PartialOrd, Ord, Eq)]
+    pub enum MessageEventType {
+        Sent,
     }
 }
@@ -9,11 +9,13 @@ use crate::{
     },
     publisher::{
         TypedEvent,
-        topic::{TopicEvent, fluvio::KeyEvent},
+        schema::Versioned,
+        topic::{TopicEvent, config::TopicConfig, fluvio::KeyEvent},
     },
 };
 
 pub mod auth;
+pub mod catalogue;
 pub mod group;
 pub mod message;
 pub mod user;
@@ -35,7 +37,10 @@ pub enum Event {
     GroupEvent(GroupEvent),
 }
 
-#[derive(Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+/// No event changed its JSON since the first schema version, so none needs upcasting yet.
+impl Versioned for Event {}
+
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub enum EventType {
     User(UserEventType),
     Auth(AuthEventType),
@@ -47,12 +52,20 @@ impl TopicEvent for EventType {
     fn event_topic(&self) -> crate::publisher::topic::Topic {
         self.inner().event_topic()
     }
+
+    fn topic_config(&self) -> TopicConfig {
+        self.inner().topic_config()
+    }
 }
 
 impl TopicEvent for Event {
     fn event_topic(&self) -> crate::publisher::topic::Topic {
         self.inner().event_topic()
     }
+
+    fn topic_config(&self) -> TopicConfig {
+        self.inner().topic_config()
+    }
 }
 
 impl KeyEvent for Event {
@@ -1,12 +1,8 @@
-use fluvio::RecordKey;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    events::EventType,
-    publisher::{
-        TypedEvent,
-        topic::{TopicEvent, fluvio::KeyEvent},
-    },
+    DevcordEvent,
+    events::{EventType, catalogue::event_type_enum},
 };
 
 #[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
@@ -25,52 +21,26 @@ pub struct FriendRequestAnswered {
     pub accepted: bool,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, DevcordEvent)]
 #[serde(tag = "kind")]
+#[event(event_type = EventType::User, types = UserEventType, samples)]
 pub enum UserEvent {
     #[serde(rename = "user_updated")]
+    #[event(type = Updated, topic = "user-updated", key = id)]
     UserUpdatedEvent(UserUpdated),
+    // Friend requests only know the username of the user who sent them.
     #[serde(rename = "friend_request_created")]
+    #[event(type = Friendship, topic = "user-friendship", key = from_username)]
     FriendRequestCreatedEvent(FriendRequestCreated),
     #[serde(rename = "friend_request_answered")]
+    #[event(type = Friendship, topic = "user-friendship", key = from_username)]
     FriendRequestAnsweredEvent(FriendRequestAnswered),
 }
 
-#[derive(Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
-pub enum UserEventType {
-    Updated,
-    Friendship,
-}
-
-impl TopicEvent for UserEvent {
-    fn event_topic(&self) -> crate::publisher::topic::Topic {
-        self.event_type().event_topic()
-    }
-}
-
-impl TopicEvent for UserEventType {
-    fn event_topic(&self) -> crate::publisher::topic::Topic {
-        match self {
-            UserEventType::Updated => "user-updated",
-            UserEventType::Friendship => "user-friendship",
-        }
-    }
-}
-
-impl KeyEvent for UserEvent {
-    fn event_key(&self) -> fluvio::RecordKey {
-        RecordKey::NULL
-    }
-}
-
-impl TypedEvent for UserEvent {
-    type EventType = EventType;
-
-    fn event_type(&self) -> Self::EventType {
-        EventType::User(match self {
-            UserEvent::UserUpdatedEvent(_) => UserEventType::Updated,
-            UserEvent::FriendRequestCreatedEvent(_) => UserEventType::Friendship,
-            UserEvent::FriendRequestAnsweredEvent(_) => UserEventType::Friendship,
-        })
+event_type_enum! {
+    #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+    pub enum UserEventType {
+        Updated,
+        Friendship,
     }
 }
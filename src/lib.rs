@@ -1,4 +1,9 @@
+// Lets the code generated by `#[derive(DevcordEvent)]` name this crate from inside it too.
+extern crate self as devcord_events;
+
 pub mod events;
 pub mod publisher;
 #[cfg(test)]
 mod tests;
+
+pub use devcord_events_derive::DevcordEvent;
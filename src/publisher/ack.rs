@@ -0,0 +1,26 @@
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, Ordering},
+};
+
+tokio::task_local! {
+    static ACKNOWLEDGED: Arc<AtomicBool>;
+}
+
+/// Acknowledges the event being handled by the calling listener, so its offset gets
+/// committed once every listener is done with it. Only needed by subscriptions using
+/// [`AckMode::Manual`](crate::publisher::options::AckMode::Manual).
+///
+/// Returns `false` when called outside of a listener.
+pub fn ack() -> bool {
+    ACKNOWLEDGED
+        .try_with(|acknowledged| acknowledged.store(true, Ordering::SeqCst))
+        .is_ok()
+}
+
+/// Runs `f`, reporting whether any listener called [`ack`] meanwhile.
+pub(crate) async fn track<F: Future>(f: F) -> (F::Output, bool) {
+    let acknowledged = Arc::new(AtomicBool::new(false));
+    let output = ACKNOWLEDGED.scope(acknowledged.clone(), f).await;
+    (output, acknowledged.load(Ordering::SeqCst))
+}
@@ -0,0 +1,277 @@
+use anyhow::{anyhow, bail};
+#[cfg(feature = "bincode")]
+use serde::Deserialize;
+use serde::{Serialize, de::DeserializeOwned};
+#[cfg(any(feature = "msgpack", feature = "cbor"))]
+use serde_json::Value;
+use std::{collections::HashMap, sync::Arc};
+#[cfg(feature = "bincode")]
+use uuid::Uuid;
+
+#[cfg(feature = "bincode")]
+use crate::publisher::envelope::Metadata;
+#[cfg(any(feature = "msgpack", feature = "cbor"))]
+use crate::publisher::schema::is_outdated;
+use crate::publisher::{envelope::Envelope, schema::Versioned, topic::Topic};
+
+pub const JSON: &str = "application/json";
+#[cfg(feature = "msgpack")]
+pub const MSGPACK: &str = "application/msgpack";
+#[cfg(feature = "cbor")]
+pub const CBOR: &str = "application/cbor";
+#[cfg(feature = "bincode")]
+pub const BINCODE: &str = "application/x-bincode";
+
+/// First byte of a framed record. JSON never starts with it, so records published
+/// before codecs existed are told apart from framed ones.
+const FRAME_MAGIC: u8 = 0;
+
+/// Turns envelopes into record values and back.
+pub trait Codec<T>: Send + Sync {
+    /// Marker written ahead of every record encoded with this codec, so consumers pick
+    /// the codec to decode it with.
+    fn content_type(&self) -> &str;
+    fn encode(&self, envelope: &Envelope<T>) -> anyhow::Result<Vec<u8>>;
+    fn decode(&self, value: &[u8]) -> anyhow::Result<Envelope<T>>;
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct JsonCodec;
+
+impl<T: Serialize + DeserializeOwned + Versioned> Codec<T> for JsonCodec {
+    fn content_type(&self) -> &str {
+        JSON
+    }
+
+    fn encode(&self, envelope: &Envelope<T>) -> anyhow::Result<Vec<u8>> {
+        Ok(serde_json::to_vec(envelope)?)
+    }
+
+    fn decode(&self, value: &[u8]) -> anyhow::Result<Envelope<T>> {
+        Envelope::from_slice(value)
+    }
+}
+
+#[cfg(feature = "msgpack")]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct MsgpackCodec;
+
+#[cfg(feature = "msgpack")]
+impl<T: Serialize + DeserializeOwned + Versioned> Codec<T> for MsgpackCodec {
+    fn content_type(&self) -> &str {
+        MSGPACK
+    }
+
+    fn encode(&self, envelope: &Envelope<T>) -> anyhow::Result<Vec<u8>> {
+        // Field names are kept, as optional metadata is left out when unset.
+        Ok(rmp_serde::to_vec_named(envelope)?)
+    }
+
+    fn decode(&self, value: &[u8]) -> anyhow::Result<Envelope<T>> {
+        decode_versioned(rmp_serde::from_slice(value).map_err(Into::into), || {
+            Ok(rmp_serde::from_slice(value)?)
+        })
+    }
+}
+
+#[cfg(feature = "cbor")]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct CborCodec;
+
+#[cfg(feature = "cbor")]
+impl<T: Serialize + DeserializeOwned + Versioned> Codec<T> for CborCodec {
+    fn content_type(&self) -> &str {
+        CBOR
+    }
+
+    fn encode(&self, envelope: &Envelope<T>) -> anyhow::Result<Vec<u8>> {
+        let mut value = Vec::new();
+        ciborium::into_writer(envelope, &mut value)?;
+        Ok(value)
+    }
+
+    fn decode(&self, value: &[u8]) -> anyhow::Result<Envelope<T>> {
+        decode_versioned(ciborium::from_reader(value).map_err(Into::into), || {
+            Ok(ciborium::from_reader(value)?)
+        })
+    }
+}
+
+/// Returns the envelope a self-describing codec decoded, unless it has an older schema
+/// version, in which case its event is decoded again with `untyped` to be upcast.
+#[cfg(any(feature = "msgpack", feature = "cbor"))]
+fn decode_versioned<T: DeserializeOwned + Versioned>(
+    typed: anyhow::Result<Envelope<T>>,
+    untyped: impl FnOnce() -> anyhow::Result<Envelope<Value>>,
+) -> anyhow::Result<Envelope<T>> {
+    if let Ok(envelope) = typed
+        && !is_outdated::<T>(envelope.metadata.schema_version)
+    {
+        return Ok(envelope);
+    }
+    Envelope::from_outdated(untyped()?)
+}
+
+/// Events bincode can decode, which [`BincodeCodec`] is limited to. Bincode is not
+/// self-describing, so their types must not be internally tagged, untagged nor flattened,
+/// which rules out [`Event`](crate::events::Event).
+#[cfg(feature = "bincode")]
+pub trait BincodeEvent: Serialize + DeserializeOwned {}
+
+/// Only decodes records for the handlers it was given to, as it only suits [`BincodeEvent`]s.
+#[cfg(feature = "bincode")]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct BincodeCodec;
+
+#[cfg(feature = "bincode")]
+impl<T: BincodeEvent + Versioned> Codec<T> for BincodeCodec {
+    fn content_type(&self) -> &str {
+        BINCODE
+    }
+
+    fn encode(&self, envelope: &Envelope<T>) -> anyhow::Result<Vec<u8>> {
+        Ok(bincode::serialize(&CompactEnvelope::from(envelope))?)
+    }
+
+    /// Records of another schema version are rejected, as there is no telling their
+    /// fields apart to upcast them.
+    fn decode(&self, value: &[u8]) -> anyhow::Result<Envelope<T>> {
+        // The fields of a `CompactEnvelope` up to its schema version.
+        let (_, _, _, schema_version) =
+            bincode::deserialize::<(Uuid, i64, Option<String>, u32)>(value)?;
+        if schema_version != T::CURRENT_VERSION {
+            bail!(
+                "bincode record of schema version {} instead of {}",
+                schema_version,
+                T::CURRENT_VERSION
+            );
+        }
+        Ok(bincode::deserialize::<CompactEnvelope<T>>(value)?.into())
+    }
+}
+
+/// [`Envelope`] laid out field by field, for formats that cannot flatten nor skip fields.
+#[cfg(feature = "bincode")]
+#[derive(Serialize, Deserialize)]
+struct CompactEnvelope<T> {
+    event_id: Uuid,
+    occurred_at: i64,
+    source_service: Option<String>,
+    schema_version: u32,
+    correlation_id: Option<Uuid>,
+    causation_id: Option<Uuid>,
+    event: T,
+}
+
+#[cfg(feature = "bincode")]
+impl<'a, T> From<&'a Envelope<T>> for CompactEnvelope<&'a T> {
+    fn from(envelope: &'a Envelope<T>) -> Self {
+        let metadata = &envelope.metadata;
+        Self {
+            event_id: metadata.event_id,
+            occurred_at: metadata.occurred_at,
+            source_service: metadata.source_service.clone(),
+            schema_version: metadata.schema_version,
+            correlation_id: metadata.correlation_id,
+            causation_id: metadata.causation_id,
+            event: &envelope.event,
+        }
+    }
+}
+
+#[cfg(feature = "bincode")]
+impl<T> From<CompactEnvelope<T>> for Envelope<T> {
+    fn from(envelope: CompactEnvelope<T>) -> Self {
+        Self {
+            metadata: Metadata {
+                event_id: envelope.event_id,
+                occurred_at: envelope.occurred_at,
+                source_service: envelope.source_service,
+                schema_version: envelope.schema_version,
+                correlation_id: envelope.correlation_id,
+                causation_id: envelope.causation_id,
+            },
+            event: envelope.event,
+            partition_key: None,
+        }
+    }
+}
+
+/// Frames `payload` as `[0, content type length, content type, payload]`.
+pub(crate) fn frame(content_type: &str, payload: &[u8]) -> anyhow::Result<Vec<u8>> {
+    let length = u8::try_from(content_type.len())
+        .map_err(|_| anyhow!("content type {content_type} is too long"))?;
+    let mut value = Vec::with_capacity(2 + content_type.len() + payload.len());
+    value.push(FRAME_MAGIC);
+    value.push(length);
+    value.extend_from_slice(content_type.as_bytes());
+    value.extend_from_slice(payload);
+    Ok(value)
+}
+
+/// Splits a record into its content type and payload. Unframed records are JSON.
+pub(crate) fn unframe(value: &[u8]) -> anyhow::Result<(&str, &[u8])> {
+    let [FRAME_MAGIC, length, rest @ ..] = value else {
+        return Ok((JSON, value));
+    };
+    let length = usize::from(*length);
+    if rest.len() < length {
+        bail!("record frame is truncated");
+    }
+    let (content_type, payload) = rest.split_at(length);
+    Ok((std::str::from_utf8(content_type)?, payload))
+}
+
+/// Codecs of a handler: the ones it encodes each topic with, and every one it knows to
+/// decode with, so a topic can hold records of several codecs while it migrates.
+pub(crate) struct Codecs<T> {
+    default: Arc<dyn Codec<T>>,
+    topics: HashMap<Topic, Arc<dyn Codec<T>>>,
+    decoders: HashMap<String, Arc<dyn Codec<T>>>,
+}
+
+impl<T: Serialize + DeserializeOwned + Versioned + 'static> Codecs<T> {
+    pub(crate) fn new(
+        default: Arc<dyn Codec<T>>,
+        topics: HashMap<Topic, Arc<dyn Codec<T>>>,
+    ) -> Self {
+        let mut decoders = HashMap::<String, Arc<dyn Codec<T>>>::new();
+        decoders.insert(JSON.to_string(), Arc::new(JsonCodec));
+        #[cfg(feature = "msgpack")]
+        decoders.insert(MSGPACK.to_string(), Arc::new(MsgpackCodec));
+        #[cfg(feature = "cbor")]
+        decoders.insert(CBOR.to_string(), Arc::new(CborCodec));
+        for codec in topics.values().chain([&default]) {
+            decoders.insert(codec.content_type().to_string(), codec.clone());
+        }
+
+        Self {
+            default,
+            topics,
+            decoders,
+        }
+    }
+}
+
+impl<T> Codecs<T> {
+    /// Encodes `envelope` with the codec of `topic`. JSON records are left unframed, so
+    /// consumers that predate codecs keep reading them.
+    pub(crate) fn encode(&self, topic: Topic, envelope: &Envelope<T>) -> anyhow::Result<Vec<u8>> {
+        let codec = self.topics.get(topic).unwrap_or(&self.default);
+        let payload = codec.encode(envelope)?;
+        match codec.content_type() {
+            JSON => Ok(payload),
+            content_type => frame(content_type, &payload),
+        }
+    }
+
+    /// Decodes a record with the codec its content type names.
+    pub(crate) fn decode(&self, value: &[u8]) -> anyhow::Result<Envelope<T>> {
+        let (content_type, payload) = unframe(value)?;
+        let codec = self
+            .decoders
+            .get(content_type)
+            .ok_or_else(|| anyhow!("no codec for content type {content_type}"))?;
+        codec.decode(payload)
+    }
+}
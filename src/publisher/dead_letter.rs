@@ -0,0 +1,67 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeadLetterReason {
+    #[serde(rename = "undecodable")]
+    Undecodable,
+    #[serde(rename = "listener_failed")]
+    ListenerFailed,
+    /// The record of a signed topic had no valid signature, and was quarantined.
+    #[serde(rename = "unverified")]
+    Unverified,
+    /// No listener of an [`AckMode::Manual`](crate::publisher::options::AckMode::Manual)
+    /// subscription acknowledged the record, however many times it was read again.
+    #[serde(rename = "unacknowledged")]
+    Unacknowledged,
+}
+
+/// A record that could not be delivered, kept with enough context to inspect and re-drive it.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct DeadLetter {
+    pub topic: String,
+    pub partition: u32,
+    pub offset: i64,
+    /// Unix time in milliseconds at which the record was dead-lettered.
+    pub timestamp: i64,
+    pub reason: DeadLetterReason,
+    pub error: String,
+    /// Key the record was published with, which it is re-driven with too. Letters recorded
+    /// before keys were kept have none.
+    #[serde(default)]
+    pub key: Option<Vec<u8>>,
+    pub payload: Vec<u8>,
+}
+
+/// Destination for records that a topic reader gives up on.
+#[async_trait]
+pub trait DeadLetterSink: Send + Sync {
+    async fn send(&self, letter: DeadLetter) -> anyhow::Result<()>;
+    async fn dead_letters(&self, topic: &str) -> anyhow::Result<Vec<DeadLetter>>;
+}
+
+/// Keeps dead letters in process memory, mostly useful for tests.
+#[derive(Default)]
+pub struct InMemoryDeadLetters {
+    letters: RwLock<Vec<DeadLetter>>,
+}
+
+#[async_trait]
+impl DeadLetterSink for InMemoryDeadLetters {
+    async fn send(&self, letter: DeadLetter) -> anyhow::Result<()> {
+        self.letters.write().await.push(letter);
+        Ok(())
+    }
+
+    async fn dead_letters(&self, topic: &str) -> anyhow::Result<Vec<DeadLetter>> {
+        Ok(self
+            .letters
+            .read()
+            .await
+            .iter()
+            .filter(|letter| letter.topic == topic)
+            .cloned()
+            .collect())
+    }
+}
@@ -0,0 +1,74 @@
+use aes_gcm::{
+    Aes256Gcm, KeyInit,
+    aead::{Aead, AeadCore, Nonce, OsRng, Payload},
+};
+use anyhow::{anyhow, bail};
+use std::borrow::Cow;
+
+use crate::publisher::keys::KeyProvider;
+
+/// First byte of an encrypted record. Neither JSON, a codec frame nor a signature starts with it.
+pub(crate) const ENCRYPTED_MAGIC: u8 = 2;
+const NONCE_LEN: usize = 12;
+
+/// Encrypts `value` with AES-256-GCM under the current key of `keys`, as
+/// `[2, key id length, key id, nonce, ciphertext]`. The key id is authenticated with it.
+pub(crate) fn encrypt(keys: &dyn KeyProvider, value: &[u8]) -> anyhow::Result<Vec<u8>> {
+    let (key_id, key) = keys.current_key()?;
+    let length = u8::try_from(key_id.len()).map_err(|_| anyhow!("key id {key_id} is too long"))?;
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    let ciphertext = cipher(&key_id, &key)?
+        .encrypt(
+            &nonce,
+            Payload {
+                msg: value,
+                aad: key_id.as_bytes(),
+            },
+        )
+        .map_err(|_| anyhow!("error encrypting record with key {key_id}"))?;
+
+    let mut encrypted = Vec::with_capacity(2 + key_id.len() + NONCE_LEN + ciphertext.len());
+    encrypted.push(ENCRYPTED_MAGIC);
+    encrypted.push(length);
+    encrypted.extend_from_slice(key_id.as_bytes());
+    encrypted.extend_from_slice(&nonce);
+    encrypted.extend_from_slice(&ciphertext);
+    Ok(encrypted)
+}
+
+/// Decrypts a record encrypted with one of the keys of `keys`, leaving others as they are.
+pub(crate) fn decrypt<'a>(
+    keys: Option<&dyn KeyProvider>,
+    record: &'a [u8],
+) -> anyhow::Result<Cow<'a, [u8]>> {
+    let [ENCRYPTED_MAGIC, length, rest @ ..] = record else {
+        return Ok(Cow::Borrowed(record));
+    };
+    let length = usize::from(*length);
+    if rest.len() < length + NONCE_LEN {
+        bail!("encrypted record is truncated");
+    }
+    let (key_id, rest) = rest.split_at(length);
+    let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+    let nonce: &Nonce<Aes256Gcm> = nonce.into();
+    let key_id = std::str::from_utf8(key_id)?;
+
+    let keys = keys.ok_or_else(|| anyhow!("record is encrypted, and no keys were given"))?;
+    let key = keys
+        .key(key_id)
+        .ok_or_else(|| anyhow!("record is encrypted with unknown key {key_id}"))?;
+    let value = cipher(key_id, &key)?
+        .decrypt(
+            nonce,
+            Payload {
+                msg: ciphertext,
+                aad: key_id.as_bytes(),
+            },
+        )
+        .map_err(|_| anyhow!("error decrypting record with key {key_id}"))?;
+    Ok(Cow::Owned(value))
+}
+
+fn cipher(key_id: &str, key: &[u8]) -> anyhow::Result<Aes256Gcm> {
+    Aes256Gcm::new_from_slice(key).map_err(|_| anyhow!("key {key_id} is not 32 bytes long"))
+}
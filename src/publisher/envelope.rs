@@ -0,0 +1,146 @@
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::publisher::{
+    now_millis,
+    schema::{Versioned, is_outdated, upcast},
+};
+
+/// Version of bare events, published before envelopes existed.
+const BARE_SCHEMA_VERSION: u32 = 1;
+
+tokio::task_local! {
+    static METADATA: Metadata;
+}
+
+/// Metadata travelling on the wire alongside every event.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Metadata {
+    pub event_id: Uuid,
+    /// Unix time in milliseconds at which the event was published.
+    pub occurred_at: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_service: Option<String>,
+    pub schema_version: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub correlation_id: Option<Uuid>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub causation_id: Option<Uuid>,
+}
+
+impl Metadata {
+    /// Fresh metadata. When created while handling another event, the new event is
+    /// recorded as caused by it and inherits its correlation id.
+    pub fn new(schema_version: u32) -> Self {
+        let cause = Self::current();
+        Self {
+            event_id: Uuid::new_v4(),
+            occurred_at: now_millis(),
+            source_service: None,
+            schema_version,
+            correlation_id: cause
+                .as_ref()
+                .map(|cause| cause.correlation_id.unwrap_or(cause.event_id)),
+            causation_id: cause.map(|cause| cause.event_id),
+        }
+    }
+
+    /// Metadata of the event being handled by the calling listener.
+    ///
+    /// Only available from the listener's own task, not from tasks it spawns
+    /// nor from [`EventStream`](crate::publisher::stream::EventStream) consumers.
+    pub fn current() -> Option<Self> {
+        METADATA.try_with(Clone::clone).ok()
+    }
+
+    pub(crate) async fn scope<F: Future>(self, f: F) -> F::Output {
+        METADATA.scope(self, f).await
+    }
+}
+
+/// Wire format of published events: the event itself plus its [`Metadata`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Envelope<T> {
+    #[serde(flatten)]
+    pub metadata: Metadata,
+    pub event: T,
+    /// Publishes the event under this key instead of its own, keeping it ordered with
+    /// other events of the same key. Not part of the wire format.
+    #[serde(skip)]
+    pub partition_key: Option<String>,
+}
+
+impl<T: Versioned> Envelope<T> {
+    /// Envelope of a new event, stamped with the current schema version of `T`.
+    pub fn new(event: T) -> Self {
+        Self {
+            metadata: Metadata::new(T::CURRENT_VERSION),
+            event,
+            partition_key: None,
+        }
+    }
+}
+
+impl<T> Envelope<T> {
+    pub fn with_source_service(mut self, source_service: impl Into<String>) -> Self {
+        self.metadata.source_service = Some(source_service.into());
+        self
+    }
+
+    pub fn with_partition_key(mut self, partition_key: impl Into<String>) -> Self {
+        self.partition_key = Some(partition_key.into());
+        self
+    }
+
+    pub fn with_correlation_id(mut self, correlation_id: Uuid) -> Self {
+        self.metadata.correlation_id = Some(correlation_id);
+        self
+    }
+
+    pub fn with_causation_id(mut self, causation_id: Uuid) -> Self {
+        self.metadata.causation_id = Some(causation_id);
+        self
+    }
+}
+
+impl<T: DeserializeOwned + Versioned> Envelope<T> {
+    /// Decodes an enveloped event, upcasting it when it has an older schema version. Also
+    /// accepts bare events published before envelopes existed, which get fresh metadata.
+    pub fn from_slice(value: &[u8]) -> anyhow::Result<Self> {
+        if let Ok(envelope) = serde_json::from_slice::<Self>(value)
+            && !is_outdated::<T>(envelope.metadata.schema_version)
+        {
+            return Ok(envelope);
+        }
+
+        let value = serde_json::from_slice::<Value>(value)?;
+        match serde_json::from_value::<Envelope<Value>>(value.clone()) {
+            Ok(envelope) => Self::from_outdated(envelope),
+            Err(_) => {
+                let mut event = value;
+                upcast::<T>(&mut event, BARE_SCHEMA_VERSION)?;
+                Ok(Self {
+                    metadata: Metadata::new(T::CURRENT_VERSION),
+                    event: serde_json::from_value(event)?,
+                    partition_key: None,
+                })
+            }
+        }
+    }
+
+    /// Brings an envelope decoded without its event type up to the current schema of `T`.
+    pub(crate) fn from_outdated(envelope: Envelope<Value>) -> anyhow::Result<Self> {
+        let Envelope {
+            metadata,
+            mut event,
+            ..
+        } = envelope;
+        upcast::<T>(&mut event, metadata.schema_version)?;
+        Ok(Self {
+            metadata,
+            event: serde_json::from_value(event)?,
+            partition_key: None,
+        })
+    }
+}
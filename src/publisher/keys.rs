@@ -0,0 +1,44 @@
+use std::collections::HashMap;
+
+/// Keys records are signed or encrypted with, looked up by id so they can rotate: records
+/// carry the id of their key, and keep being read for as long as the provider knows it.
+pub trait KeyProvider: Send + Sync {
+    /// Id and material of the key new records use.
+    fn current_key(&self) -> anyhow::Result<(String, Vec<u8>)>;
+    /// Material of the key `key_id`, or `None` when it is unknown or no longer trusted.
+    fn key(&self, key_id: &str) -> Option<Vec<u8>>;
+}
+
+/// A fixed set of keys. Keys other than the current one only read records published
+/// before the rotation.
+#[derive(Debug, Clone)]
+pub struct StaticKeys {
+    current: String,
+    keys: HashMap<String, Vec<u8>>,
+}
+
+impl StaticKeys {
+    pub fn new(key_id: impl Into<String>, key: impl Into<Vec<u8>>) -> Self {
+        let current = key_id.into();
+        Self {
+            keys: HashMap::from([(current.clone(), key.into())]),
+            current,
+        }
+    }
+
+    /// Keeps `key_id` known without using it for new records.
+    pub fn with_key(mut self, key_id: impl Into<String>, key: impl Into<Vec<u8>>) -> Self {
+        self.keys.insert(key_id.into(), key.into());
+        self
+    }
+}
+
+impl KeyProvider for StaticKeys {
+    fn current_key(&self) -> anyhow::Result<(String, Vec<u8>)> {
+        Ok((self.current.clone(), self.keys[&self.current].clone()))
+    }
+
+    fn key(&self, key_id: &str) -> Option<Vec<u8>> {
+        self.keys.get(key_id).cloned()
+    }
+}
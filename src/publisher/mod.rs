@@ -1,11 +1,95 @@
-use std::{hash::Hash, pin::Pin};
+use std::{
+    hash::Hash,
+    pin::Pin,
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
 
 use async_trait::async_trait;
 
+use crate::publisher::{
+    envelope::Envelope, options::SubscribeOptions, schema::Versioned, stream::EventStream,
+    subscribers::Unsubscribe,
+};
+
+pub mod ack;
+pub mod codec;
+pub mod dead_letter;
+pub mod encryption;
+pub mod envelope;
+pub mod keys;
+pub mod options;
+pub mod retry;
+pub mod schema;
+pub mod signing;
+pub mod stream;
+mod subscribers;
 pub mod topic;
 
-type EventSubscriberHdlrFn<T> =
-    Box<dyn (FnMut(T) -> Pin<Box<dyn Future<Output = ()> + Send + 'static>>) + Send + Sync>;
+type EventSubscriberHdlrFn<T> = Box<
+    dyn (FnMut(T) -> Pin<Box<dyn Future<Output = anyhow::Result<()>> + Send + 'static>>)
+        + Send
+        + Sync,
+>;
+
+/// Handle identifying a single listener registered through [`EventManager::subscribe`].
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SubscriptionId(u64);
+
+/// Guard returned by [`EventManager::subscribe`] that unsubscribes its listener when dropped.
+///
+/// Use [`Subscription::detach`] to keep the listener registered until
+/// [`EventManager::unsubscribe`] is called explicitly.
+#[must_use = "dropping a Subscription unsubscribes its listener"]
+pub struct Subscription {
+    id: SubscriptionId,
+    subscribers: Option<Arc<dyn Unsubscribe>>,
+}
+
+impl Subscription {
+    pub(crate) fn new(id: SubscriptionId, subscribers: Arc<dyn Unsubscribe>) -> Self {
+        Self {
+            id,
+            subscribers: Some(subscribers),
+        }
+    }
+
+    pub fn id(&self) -> SubscriptionId {
+        self.id
+    }
+
+    /// Releases the guard without unsubscribing, returning the id needed to do it later.
+    pub fn detach(mut self) -> SubscriptionId {
+        self.subscribers = None;
+        self.id
+    }
+
+    /// Unsubscribes right away, waiting for the topic reader to be released.
+    pub async fn unsubscribe(mut self) {
+        if let Some(subscribers) = self.subscribers.take() {
+            subscribers.unsubscribe(self.id).await;
+        }
+    }
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        let Some(subscribers) = self.subscribers.take() else {
+            return;
+        };
+        let id = self.id;
+        if let Ok(runtime) = tokio::runtime::Handle::try_current() {
+            runtime.spawn(async move { subscribers.unsubscribe(id).await });
+        }
+    }
+}
+
+/// Current Unix time in milliseconds, the unit used for timestamps across events.
+pub(crate) fn now_millis() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_millis() as i64)
+}
 
 pub trait TypedEvent {
     type EventType: Eq + Hash + Clone + Send + Sync;
@@ -13,13 +97,26 @@ pub trait TypedEvent {
 }
 
 #[async_trait]
-pub trait EventManager {
-    type Event: TypedEvent;
+pub trait EventManager: Send + Sync {
+    type Event: TypedEvent + Versioned + Send;
     async fn subscribe(
         &self,
         event: Self::Event,
         listener: EventSubscriberHdlrFn<Self::Event>,
-    ) -> anyhow::Result<()>;
-    async fn unsubscribe(&self, event: Self::Event) -> anyhow::Result<()>;
-    async fn notify(&self, event: Self::Event) -> anyhow::Result<()>;
+    ) -> anyhow::Result<Subscription>;
+    async fn subscribe_with(
+        &self,
+        event: Self::Event,
+        listener: EventSubscriberHdlrFn<Self::Event>,
+        options: SubscribeOptions,
+    ) -> anyhow::Result<Subscription>;
+    async fn subscribe_stream(
+        &self,
+        event_type: <Self::Event as TypedEvent>::EventType,
+    ) -> anyhow::Result<EventStream<Self::Event>>;
+    async fn unsubscribe(&self, subscription: SubscriptionId) -> anyhow::Result<()>;
+    async fn notify(&self, event: Self::Event) -> anyhow::Result<()> {
+        self.notify_envelope(Envelope::new(event)).await
+    }
+    async fn notify_envelope(&self, envelope: Envelope<Self::Event>) -> anyhow::Result<()>;
 }
@@ -0,0 +1,99 @@
+use crate::publisher::retry::RetryPolicy;
+
+/// Where a topic reader starts when its consumer has no committed offset.
+#[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq)]
+pub enum StartOffset {
+    Beginning,
+    #[default]
+    End,
+    Absolute(i64),
+    /// That many records before the end of each partition.
+    FromEnd(u32),
+    /// The first record published at or after this time, in milliseconds since the Unix
+    /// epoch. Read from the beginning, skipping the records published earlier.
+    Timestamp(i64),
+}
+
+/// When a topic reader commits the offset of a record for its consumer name, which
+/// is where the consumer resumes after a restart.
+#[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq)]
+pub enum AckMode {
+    /// Offsets are never committed, every reader starts from its [`StartOffset`].
+    #[default]
+    None,
+    /// Committed once every listener handled the record or it was dead-lettered. Records that
+    /// could not be dead-lettered are read again.
+    AfterHandle,
+    /// Committed once handled, only if a listener called [`ack`](crate::publisher::ack::ack),
+    /// or once dead-lettered. Records no listener acknowledged are read again, before the
+    /// records after them, until the handler's redelivery policy dead-letters them.
+    Manual,
+}
+
+/// Settings of the topic reader behind a subscription. Subscriptions to the same
+/// topic share a reader only when these are equal.
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct ConsumerOptions {
+    pub start: StartOffset,
+    pub consumer_name: String,
+    /// Partitions to read from, all of them when empty.
+    pub partitions: Vec<u32>,
+    pub max_bytes: Option<i32>,
+    pub ack: AckMode,
+}
+
+impl Default for ConsumerOptions {
+    fn default() -> Self {
+        Self {
+            start: StartOffset::default(),
+            consumer_name: String::from("consumer-auto"),
+            partitions: Vec::new(),
+            max_bytes: None,
+            ack: AckMode::default(),
+        }
+    }
+}
+
+/// Per-subscription settings passed to [`EventManager::subscribe_with`].
+///
+/// Consumer settings only apply to brokers that keep a log, the in-memory
+/// handler just uses them to tell readers apart.
+///
+/// [`EventManager::subscribe_with`]: crate::publisher::EventManager::subscribe_with
+#[derive(Debug, Clone, Default)]
+pub struct SubscribeOptions {
+    pub consumer: ConsumerOptions,
+    pub retry: RetryPolicy,
+}
+
+impl SubscribeOptions {
+    pub fn with_retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    pub fn with_start(mut self, start: StartOffset) -> Self {
+        self.consumer.start = start;
+        self
+    }
+
+    pub fn with_consumer_name(mut self, consumer_name: impl Into<String>) -> Self {
+        self.consumer.consumer_name = consumer_name.into();
+        self
+    }
+
+    pub fn with_partitions(mut self, partitions: impl IntoIterator<Item = u32>) -> Self {
+        self.consumer.partitions = partitions.into_iter().collect();
+        self
+    }
+
+    pub fn with_max_bytes(mut self, max_bytes: i32) -> Self {
+        self.consumer.max_bytes = Some(max_bytes);
+        self
+    }
+
+    pub fn with_ack(mut self, ack: AckMode) -> Self {
+        self.consumer.ack = ack;
+        self
+    }
+}
@@ -0,0 +1,67 @@
+use std::time::Duration;
+
+/// How many times a failing listener is run again before the event is given up on,
+/// and how long to wait between attempts.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    /// Total attempts, including the first one. `1` disables retries.
+    pub max_attempts: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    pub multiplier: f64,
+    /// Fraction of each backoff, between `0.0` and `1.0`, that is randomly shaved off.
+    pub jitter: f64,
+}
+
+impl RetryPolicy {
+    pub fn none() -> Self {
+        Self::exponential(1)
+    }
+
+    pub fn exponential(max_attempts: u32) -> Self {
+        Self {
+            max_attempts,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(30),
+            multiplier: 2.0,
+            jitter: 0.2,
+        }
+    }
+
+    pub fn with_backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_backoff = initial;
+        self.max_backoff = max;
+        self
+    }
+
+    pub fn with_multiplier(mut self, multiplier: f64) -> Self {
+        self.multiplier = multiplier;
+        self
+    }
+
+    pub fn with_jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Delay before the given retry, counting from `1` for the first one.
+    pub fn backoff(&self, retry: u32) -> Duration {
+        if self.initial_backoff.is_zero() {
+            return Duration::ZERO;
+        }
+        // Computed in seconds and clamped before building the `Duration`, which the
+        // unclamped backoff overflows after a few dozen retries.
+        let exponent = retry.saturating_sub(1).min(i32::MAX as u32) as i32;
+        let secs = (self.initial_backoff.as_secs_f64() * self.multiplier.max(1.0).powi(exponent))
+            .min(self.max_backoff.as_secs_f64())
+            * (1.0 - self.jitter * fastrand::f64());
+        Duration::try_from_secs_f64(secs)
+            .map_or(self.max_backoff, |backoff| backoff.min(self.max_backoff))
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::none()
+    }
+}
@@ -0,0 +1,53 @@
+use serde_json::Value;
+
+/// Migrates the JSON of an event from schema version `from_version` to the next one.
+#[derive(Debug, Clone, Copy)]
+pub struct Upcaster {
+    pub from_version: u32,
+    pub upcast: fn(&mut Value) -> anyhow::Result<()>,
+}
+
+impl Upcaster {
+    pub const fn new(from_version: u32, upcast: fn(&mut Value) -> anyhow::Result<()>) -> Self {
+        Self {
+            from_version,
+            upcast,
+        }
+    }
+}
+
+/// Events whose schema changed over time. Records published with an older
+/// [`schema_version`](crate::publisher::envelope::Metadata::schema_version) go through every
+/// upcaster from their version on before they are deserialized.
+///
+/// The version covers every kind of the event type at once, so changing one kind bumps it for
+/// all of them, and upcasters leave the JSON of the kinds they do not migrate as it is.
+///
+/// JSON, MessagePack and CBOR records are upcast through their JSON form. Bincode records
+/// cannot be, so those of another version are rejected.
+pub trait Versioned {
+    /// Version stamped on the envelope of the events published. Bump it along with an
+    /// [`Upcaster`] from the previous one whenever the JSON of any of its kinds changes.
+    const CURRENT_VERSION: u32 = 1;
+
+    fn upcasters() -> Vec<Upcaster> {
+        Vec::new()
+    }
+}
+
+/// Whether an event of `version` predates the current schema of `T`.
+pub(crate) fn is_outdated<T: Versioned>(version: u32) -> bool {
+    version < T::CURRENT_VERSION
+}
+
+/// Brings the JSON of an event of `version` up to the current schema.
+pub(crate) fn upcast<T: Versioned>(event: &mut Value, version: u32) -> anyhow::Result<()> {
+    let mut upcasters = T::upcasters();
+    upcasters.sort_by_key(|upcaster| upcaster.from_version);
+    for upcaster in upcasters {
+        if (version..T::CURRENT_VERSION).contains(&upcaster.from_version) {
+            (upcaster.upcast)(event)?;
+        }
+    }
+    Ok(())
+}
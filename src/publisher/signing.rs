@@ -0,0 +1,94 @@
+use anyhow::anyhow;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use thiserror::Error;
+
+use crate::publisher::keys::KeyProvider;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// First byte of a signed record. Neither JSON nor a codec frame starts with it.
+const SIGNED_MAGIC: u8 = 1;
+const SIGNATURE_LEN: usize = 32;
+
+/// Why a record was refused by the reader of a signed topic.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum SignatureError {
+    #[error("record is not signed")]
+    Unsigned,
+    #[error("record signature is malformed")]
+    Malformed,
+    #[error("record is signed with unknown key {0}")]
+    UnknownKey(String),
+    #[error("record signature does not match")]
+    Mismatch,
+}
+
+/// Signs `value` with the current key of `keys`, as
+/// `[1, key id length, key id, HMAC-SHA256 of key id and value, value]`.
+pub(crate) fn sign(keys: &dyn KeyProvider, value: &[u8]) -> anyhow::Result<Vec<u8>> {
+    let (key_id, key) = keys.current_key()?;
+    let length = u8::try_from(key_id.len()).map_err(|_| anyhow!("key id {key_id} is too long"))?;
+    let signature = mac(&key, &key_id, value).finalize().into_bytes();
+
+    let mut signed = Vec::with_capacity(2 + key_id.len() + SIGNATURE_LEN + value.len());
+    signed.push(SIGNED_MAGIC);
+    signed.push(length);
+    signed.extend_from_slice(key_id.as_bytes());
+    signed.extend_from_slice(&signature);
+    signed.extend_from_slice(value);
+    Ok(signed)
+}
+
+/// Checks the signature of a record, returning the value it signs.
+pub(crate) fn verify<'a>(
+    keys: &dyn KeyProvider,
+    record: &'a [u8],
+) -> Result<&'a [u8], SignatureError> {
+    let signed = split(record)?.ok_or(SignatureError::Unsigned)?;
+    let key = keys
+        .key(signed.key_id)
+        .ok_or_else(|| SignatureError::UnknownKey(signed.key_id.to_string()))?;
+    mac(&key, signed.key_id, signed.value)
+        .verify_slice(signed.signature)
+        .map_err(|_| SignatureError::Mismatch)?;
+    Ok(signed.value)
+}
+
+/// Value of a record read from a topic that is not signed, dropping a signature it may carry.
+pub(crate) fn unsigned(record: &[u8]) -> Result<&[u8], SignatureError> {
+    Ok(split(record)?.map_or(record, |signed| signed.value))
+}
+
+struct Signed<'a> {
+    key_id: &'a str,
+    signature: &'a [u8],
+    value: &'a [u8],
+}
+
+/// Splits a signed record into its key id, signature and value, or `None` when unsigned.
+fn split(record: &[u8]) -> Result<Option<Signed<'_>>, SignatureError> {
+    let [SIGNED_MAGIC, length, rest @ ..] = record else {
+        return Ok(None);
+    };
+    let length = usize::from(*length);
+    if rest.len() < length + SIGNATURE_LEN {
+        return Err(SignatureError::Malformed);
+    }
+    let (key_id, rest) = rest.split_at(length);
+    let (signature, value) = rest.split_at(SIGNATURE_LEN);
+    let key_id = std::str::from_utf8(key_id).map_err(|_| SignatureError::Malformed)?;
+    Ok(Some(Signed {
+        key_id,
+        signature,
+        value,
+    }))
+}
+
+fn mac(key: &[u8], key_id: &str, value: &[u8]) -> HmacSha256 {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC takes keys of any length");
+    mac.update(&[key_id.len() as u8]);
+    mac.update(key_id.as_bytes());
+    mac.update(value);
+    mac
+}
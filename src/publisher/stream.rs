@@ -0,0 +1,54 @@
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tokio::sync::mpsc;
+use tokio_stream::{Stream, wrappers::ReceiverStream};
+
+use crate::publisher::{EventSubscriberHdlrFn, Subscription, SubscriptionId};
+
+const STREAM_CAPACITY: usize = 16;
+
+/// Events of one type delivered as a [`Stream`], returned by [`EventManager::subscribe_stream`].
+///
+/// The buffer is bounded, so a slow consumer holds back the topic reader instead of
+/// piling up events. Dropping the stream unsubscribes it.
+///
+/// [`EventManager::subscribe_stream`]: crate::publisher::EventManager::subscribe_stream
+pub struct EventStream<T> {
+    events: ReceiverStream<T>,
+    subscription: Subscription,
+}
+
+impl<T: Send + 'static> EventStream<T> {
+    pub(crate) fn channel() -> (EventSubscriberHdlrFn<T>, mpsc::Receiver<T>) {
+        let (tx, rx) = mpsc::channel(STREAM_CAPACITY);
+        let listener: EventSubscriberHdlrFn<T> = Box::new(move |event| {
+            let tx = tx.clone();
+            Box::pin(async move {
+                let _ = tx.send(event).await;
+                Ok(())
+            })
+        });
+        (listener, rx)
+    }
+
+    pub(crate) fn new(events: mpsc::Receiver<T>, subscription: Subscription) -> Self {
+        Self {
+            events: ReceiverStream::new(events),
+            subscription,
+        }
+    }
+
+    pub fn id(&self) -> SubscriptionId {
+        self.subscription.id()
+    }
+}
+
+impl<T> Stream for EventStream<T> {
+    type Item = T;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.events).poll_next(cx)
+    }
+}
@@ -0,0 +1,308 @@
+use async_trait::async_trait;
+use std::{
+    collections::HashMap,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+use tokio::{
+    sync::{Mutex, RwLock, watch},
+    task::JoinHandle,
+    time::sleep,
+};
+use tracing::{error, warn};
+
+use crate::publisher::{
+    EventSubscriberHdlrFn, Subscription, SubscriptionId, TypedEvent,
+    options::{ConsumerOptions, SubscribeOptions},
+    retry::RetryPolicy,
+    topic::Topic,
+};
+
+/// Identifies a topic reader: one per topic and distinct consumer settings.
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub(crate) struct ReaderKey {
+    pub(crate) topic: Topic,
+    pub(crate) consumer: ConsumerOptions,
+}
+
+type ReceiversMap = HashMap<ReaderKey, Reader>;
+
+struct Reader {
+    listeners: usize,
+    handle: JoinHandle<()>,
+    stop: watch::Sender<bool>,
+}
+
+/// Tells a reader task to stop pulling records, letting it finish the one it is handling.
+pub(crate) struct StopSignal(watch::Receiver<bool>);
+
+impl StopSignal {
+    pub(crate) async fn stopped(&mut self) {
+        let _ = self.0.wait_for(|stop| *stop).await;
+    }
+}
+
+struct Subscriber<T> {
+    id: SubscriptionId,
+    // Locked only while the listener runs, so dispatch can call it without holding the registry.
+    listener: Arc<Mutex<EventSubscriberHdlrFn<T>>>,
+    consumer: ConsumerOptions,
+    retry: RetryPolicy,
+}
+
+impl<T> Clone for Subscriber<T> {
+    fn clone(&self) -> Self {
+        Self {
+            id: self.id,
+            listener: self.listener.clone(),
+            consumer: self.consumer.clone(),
+            retry: self.retry.clone(),
+        }
+    }
+}
+
+impl<T: Clone> Subscriber<T> {
+    /// Runs the listener, retrying it as its policy allows.
+    async fn handle(&self, event: &T) -> anyhow::Result<()> {
+        let mut listener = self.listener.lock().await;
+        let mut attempt = 1;
+        loop {
+            let Err(e) = (listener)(event.clone()).await else {
+                return Ok(());
+            };
+            if attempt >= self.retry.max_attempts {
+                return Err(e);
+            }
+
+            let backoff = self.retry.backoff(attempt);
+            warn!(
+                "Listener {:?} failed on attempt {}, retrying in {:?}: {}",
+                self.id, attempt, backoff, e
+            );
+            sleep(backoff).await;
+            attempt += 1;
+        }
+    }
+}
+
+/// Listeners registered on a handler, grouped by the event type they listen to.
+struct SubscriberRegistry<T: TypedEvent> {
+    next_id: u64,
+    readers: HashMap<SubscriptionId, (T::EventType, ReaderKey)>,
+    listeners: HashMap<T::EventType, Vec<Subscriber<T>>>,
+}
+
+impl<T: TypedEvent> Default for SubscriberRegistry<T> {
+    fn default() -> Self {
+        Self {
+            next_id: 0,
+            readers: Default::default(),
+            listeners: Default::default(),
+        }
+    }
+}
+
+impl<T: TypedEvent> SubscriberRegistry<T> {
+    fn insert(
+        &mut self,
+        event_type: T::EventType,
+        reader: ReaderKey,
+        listener: EventSubscriberHdlrFn<T>,
+        retry: RetryPolicy,
+    ) -> SubscriptionId {
+        let id = SubscriptionId(self.next_id);
+        self.next_id += 1;
+
+        let consumer = reader.consumer.clone();
+        self.readers.insert(id, (event_type.clone(), reader));
+        self.listeners
+            .entry(event_type)
+            .or_default()
+            .push(Subscriber {
+                id,
+                listener: Arc::new(Mutex::new(listener)),
+                consumer,
+                retry,
+            });
+        id
+    }
+
+    /// Removes a single subscription, returning the reader it was attached to.
+    fn remove(&mut self, id: SubscriptionId) -> Option<ReaderKey> {
+        let (event_type, reader) = self.readers.remove(&id)?;
+
+        if let Some(listeners) = self.listeners.get_mut(&event_type) {
+            listeners.retain(|subscriber| subscriber.id != id);
+            if listeners.is_empty() {
+                self.listeners.remove(&event_type);
+            }
+        }
+        Some(reader)
+    }
+
+    fn listeners(
+        &self,
+        event_type: &T::EventType,
+        consumer: &ConsumerOptions,
+    ) -> Vec<Subscriber<T>> {
+        self.listeners
+            .get(event_type)
+            .into_iter()
+            .flatten()
+            .filter(|subscriber| subscriber.consumer == *consumer)
+            .cloned()
+            .collect()
+    }
+}
+
+/// Subscription bookkeeping shared by every handler: the listeners and the
+/// reader tasks they need, each kept alive while at least one listener reads from it.
+pub(crate) struct Subscribers<T: TypedEvent> {
+    registry: RwLock<SubscriberRegistry<T>>,
+    receivers: RwLock<ReceiversMap>,
+    /// Set once closed, under the `receivers` lock.
+    closed: AtomicBool,
+}
+
+impl<T: TypedEvent> Default for Subscribers<T> {
+    fn default() -> Self {
+        Self {
+            registry: Default::default(),
+            receivers: Default::default(),
+            closed: AtomicBool::new(false),
+        }
+    }
+}
+
+impl<T: TypedEvent> Subscribers<T> {
+    pub(crate) fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::SeqCst)
+    }
+}
+
+impl<T> Subscribers<T>
+where
+    T: TypedEvent + Send + Sync + 'static,
+{
+    /// Registers `listener`, spawning its topic reader with `new_reader` if there is none yet.
+    pub(crate) async fn subscribe<F>(
+        self: &Arc<Self>,
+        event_type: T::EventType,
+        topic: Topic,
+        listener: EventSubscriberHdlrFn<T>,
+        options: SubscribeOptions,
+        new_reader: F,
+    ) -> anyhow::Result<Subscription>
+    where
+        F: AsyncFnOnce(&ReaderKey, StopSignal) -> anyhow::Result<JoinHandle<()>>,
+    {
+        let SubscribeOptions { consumer, retry } = options;
+        let key = ReaderKey { topic, consumer };
+        let id = self
+            .registry
+            .write()
+            .await
+            .insert(event_type, key.clone(), listener, retry);
+
+        let mut lock = self.receivers.write().await;
+        if self.is_closed() {
+            self.registry.write().await.remove(id);
+            anyhow::bail!("Handler was shut down");
+        }
+        if let Some(reader) = lock.get_mut(&key) {
+            reader.listeners += 1;
+        } else {
+            let (stop, signal) = watch::channel(false);
+            match new_reader(&key, StopSignal(signal)).await {
+                Ok(handle) => {
+                    let reader = Reader {
+                        listeners: 1,
+                        handle,
+                        stop,
+                    };
+                    lock.insert(key, reader);
+                }
+                Err(e) => {
+                    self.registry.write().await.remove(id);
+                    return Err(e);
+                }
+            }
+        }
+        Ok(Subscription::new(id, self.clone()))
+    }
+
+    pub(crate) async fn unsubscribe(&self, subscription: SubscriptionId) {
+        let Some(key) = self.registry.write().await.remove(subscription) else {
+            return;
+        };
+
+        let mut lock = self.receivers.write().await;
+        if let Some(reader) = lock.get_mut(&key) {
+            reader.listeners -= 1;
+            if reader.listeners == 0 {
+                let _ = reader.stop.send(true);
+                lock.remove(&key);
+            }
+        }
+    }
+
+    /// Stops every reader and drops every subscription, refusing new ones from then on.
+    /// Returns the reader tasks so the caller can wait for the records they are still handling.
+    pub(crate) async fn close(&self) -> Vec<(ReaderKey, JoinHandle<()>)> {
+        let mut lock = self.receivers.write().await;
+        self.closed.store(true, Ordering::SeqCst);
+        *self.registry.write().await = SubscriberRegistry::default();
+        lock.drain()
+            .map(|(key, reader)| {
+                let _ = reader.stop.send(true);
+                (key, reader.handle)
+            })
+            .collect()
+    }
+
+    /// Hands `event`, read by the `reader` task, to every listener of its event type attached
+    /// to that reader, returning the errors of those that still failed after their retries.
+    ///
+    /// The listeners are picked before any of them runs, so they are free to subscribe or
+    /// unsubscribe, and one removed meanwhile still gets this event.
+    pub(crate) async fn dispatch(
+        &self,
+        reader: &ReaderKey,
+        event: T,
+    ) -> Vec<(SubscriptionId, anyhow::Error)>
+    where
+        T: Clone,
+    {
+        let listeners = self
+            .registry
+            .read()
+            .await
+            .listeners(&event.event_type(), &reader.consumer);
+
+        let mut failures = Vec::new();
+        for subscriber in listeners {
+            if let Err(e) = subscriber.handle(&event).await {
+                error!("Listener {:?} gave up on event: {}", subscriber.id, e);
+                failures.push((subscriber.id, e));
+            }
+        }
+        failures
+    }
+}
+
+#[async_trait]
+pub(crate) trait Unsubscribe: Send + Sync {
+    async fn unsubscribe(&self, subscription: SubscriptionId);
+}
+
+#[async_trait]
+impl<T> Unsubscribe for Subscribers<T>
+where
+    T: TypedEvent + Send + Sync + 'static,
+{
+    async fn unsubscribe(&self, subscription: SubscriptionId) {
+        Subscribers::unsubscribe(self, subscription).await
+    }
+}
@@ -0,0 +1,81 @@
+use std::time::Duration;
+
+/// Compression of the records of a topic, applied by the producers of a handler and
+/// undone by its readers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TopicCompression {
+    /// Records are stored as the producer compressed them, which is not at all for handlers.
+    #[default]
+    Any,
+    None,
+    Gzip,
+    Snappy,
+    Lz4,
+    Zstd,
+}
+
+/// What happens to old records of a topic.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CleanupPolicy {
+    /// Whole segments are deleted once their records are older than the retention time.
+    #[default]
+    Delete,
+}
+
+/// Settings a topic is created with. Topics that already exist are left as they are.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TopicConfig {
+    pub partitions: u32,
+    pub replication_factor: u32,
+    /// The broker default when `None`.
+    pub retention: Option<Duration>,
+    /// Maximum size of a segment in bytes, the broker default when `None`.
+    pub max_segment_size: Option<u32>,
+    pub compression: TopicCompression,
+    pub cleanup_policy: CleanupPolicy,
+}
+
+impl TopicConfig {
+    pub fn with_partitions(mut self, partitions: u32) -> Self {
+        self.partitions = partitions;
+        self
+    }
+
+    pub fn with_replication_factor(mut self, replication_factor: u32) -> Self {
+        self.replication_factor = replication_factor;
+        self
+    }
+
+    pub fn with_retention(mut self, retention: Duration) -> Self {
+        self.retention = Some(retention);
+        self
+    }
+
+    pub fn with_max_segment_size(mut self, max_segment_size: u32) -> Self {
+        self.max_segment_size = Some(max_segment_size);
+        self
+    }
+
+    pub fn with_compression(mut self, compression: TopicCompression) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    pub fn with_cleanup_policy(mut self, cleanup_policy: CleanupPolicy) -> Self {
+        self.cleanup_policy = cleanup_policy;
+        self
+    }
+}
+
+impl Default for TopicConfig {
+    fn default() -> Self {
+        Self {
+            partitions: 1,
+            replication_factor: 1,
+            retention: None,
+            max_segment_size: None,
+            compression: TopicCompression::default(),
+            cleanup_policy: CleanupPolicy::default(),
+        }
+    }
+}
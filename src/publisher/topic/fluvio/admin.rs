@@ -0,0 +1,108 @@
+use fluvio::{Fluvio, metadata::topic::TopicSpec};
+
+use crate::publisher::topic::{
+    Topic,
+    config::TopicConfig,
+    fluvio::{
+        Error, config_mismatches,
+        dead_letter::{dead_letter_topic, quarantine_topic},
+        namespaced, topic_spec,
+    },
+};
+
+/// How a topic on the cluster compares to the configuration it should have.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TopicStatus {
+    Missing,
+    UpToDate,
+    /// The topic exists with other settings, each one described.
+    Drifted(Vec<String>),
+    /// The topic is in the namespace but no event is published to it. Topics of other
+    /// namespaces, nested ones included, are left out.
+    Unknown,
+}
+
+/// Audits and provisions the topics of a namespace ahead of the handlers, which
+/// otherwise create them when first publishing or subscribing.
+pub struct TopicAdmin {
+    fluvio: Fluvio,
+    namespace: Option<String>,
+}
+
+impl TopicAdmin {
+    pub async fn connect(namespace: Option<String>) -> anyhow::Result<Self> {
+        let fluvio = Fluvio::connect()
+            .await
+            .map_err(Error::ErrorConnectingToFluvio)?;
+        Ok(Self { fluvio, namespace })
+    }
+
+    /// Name of `topic` on the cluster.
+    pub fn topic_name(&self, topic: &str) -> String {
+        namespaced(self.namespace.as_deref(), topic)
+    }
+
+    /// Compares the cluster to `topics`, returning the status of each of them followed by the
+    /// unknown topics of the namespace. Dead letter and quarantine topics of known topics are
+    /// not unknown.
+    pub async fn status(
+        &self,
+        topics: &[(Topic, TopicConfig)],
+    ) -> anyhow::Result<Vec<(String, TopicStatus)>> {
+        let existing = self
+            .fluvio
+            .admin()
+            .await
+            .all::<TopicSpec>()
+            .await
+            .map_err(Error::ErrorAcquiringTopics)?;
+
+        let mut statuses = Vec::new();
+        let mut known = Vec::new();
+        for (topic, config) in topics {
+            let name = self.topic_name(topic);
+            let status = match existing.iter().find(|existing| existing.name == name) {
+                None => TopicStatus::Missing,
+                Some(existing) => match config_mismatches(config, &existing.spec) {
+                    mismatches if mismatches.is_empty() => TopicStatus::UpToDate,
+                    mismatches => TopicStatus::Drifted(mismatches),
+                },
+            };
+            known.push(dead_letter_topic(&name));
+            known.push(quarantine_topic(&name));
+            known.push(name.clone());
+            statuses.push((name, status));
+        }
+
+        for existing in existing {
+            if in_namespace(self.namespace.as_deref(), &existing.name)
+                && !known.contains(&existing.name)
+            {
+                statuses.push((existing.name, TopicStatus::Unknown));
+            }
+        }
+        Ok(statuses)
+    }
+
+    pub async fn create(&self, topic: Topic, config: &TopicConfig) -> anyhow::Result<()> {
+        self.fluvio
+            .admin()
+            .await
+            .create(self.topic_name(topic), false, topic_spec(config))
+            .await
+            .map_err(Error::ErrorCreatingTopic)?;
+        Ok(())
+    }
+
+    /// Deletes the topic called `name` on the cluster, namespace included.
+    pub async fn delete(&self, name: &str) -> anyhow::Result<()> {
+        self.fluvio.admin().await.delete::<TopicSpec>(name).await
+    }
+}
+
+/// Whether the topic called `name` is in `namespace`. Topic names have no dot, so those of
+/// nested namespaces, such as `staging.eu.auth-signed`, are not in `staging`.
+pub(crate) fn in_namespace(namespace: Option<&str>, name: &str) -> bool {
+    name.strip_prefix(&namespaced(namespace, ""))
+        .is_some_and(|topic| !topic.contains('.'))
+}
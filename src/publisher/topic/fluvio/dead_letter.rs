@@ -0,0 +1,111 @@
+use async_trait::async_trait;
+use fluvio::{
+    Fluvio, Offset, RecordKey, TopicProducer, consumer::ConsumerConfigExtBuilder,
+    spu::SpuSocketPool,
+};
+use serde_json::{from_slice, to_vec};
+use std::sync::Arc;
+use tokio_stream::StreamExt;
+
+use crate::publisher::{
+    dead_letter::{DeadLetter, DeadLetterSink},
+    topic::{
+        config::TopicConfig,
+        fluvio::{Error, OnceMap, once_cell, topic_exists, try_create_topic},
+    },
+};
+
+const DEAD_LETTER_SUFFIX: &str = "-dlq";
+const QUARANTINE_SUFFIX: &str = "-quarantine";
+
+pub fn dead_letter_topic(topic: &str) -> String {
+    format!("{topic}{DEAD_LETTER_SUFFIX}")
+}
+
+pub fn quarantine_topic(topic: &str) -> String {
+    format!("{topic}{QUARANTINE_SUFFIX}")
+}
+
+/// Default [`DeadLetterSink`] of [`FluvioHandler`](super::FluvioHandler), writing every
+/// dead letter to the `<topic>-dlq` topic next to the one it was read from.
+pub struct FluvioDeadLetters {
+    fluvio: Arc<Fluvio>,
+    suffix: &'static str,
+    producers: OnceMap<String, TopicProducer<SpuSocketPool>>,
+}
+
+impl FluvioDeadLetters {
+    pub fn new(fluvio: Arc<Fluvio>) -> Self {
+        Self {
+            fluvio,
+            suffix: DEAD_LETTER_SUFFIX,
+            producers: Default::default(),
+        }
+    }
+
+    /// Writes to the `<topic>-quarantine` topics instead, keeping records that failed their
+    /// signature check apart from the ones to re-drive.
+    pub fn quarantine(fluvio: Arc<Fluvio>) -> Self {
+        Self {
+            suffix: QUARANTINE_SUFFIX,
+            ..Self::new(fluvio)
+        }
+    }
+
+    fn topic(&self, topic: &str) -> String {
+        format!("{topic}{}", self.suffix)
+    }
+}
+
+#[async_trait]
+impl DeadLetterSink for FluvioDeadLetters {
+    async fn send(&self, letter: DeadLetter) -> anyhow::Result<()> {
+        let topic = self.topic(&letter.topic);
+
+        let producer = once_cell(&self.producers, &topic).await;
+        let producer = producer
+            .get_or_try_init(|| async {
+                try_create_topic(&self.fluvio, &topic, &TopicConfig::default()).await?;
+                let producer = self
+                    .fluvio
+                    .topic_producer(topic.clone())
+                    .await
+                    .map_err(Error::ErrorCreatingProducer)?;
+                anyhow::Ok(producer)
+            })
+            .await?;
+        producer
+            .send(RecordKey::NULL, to_vec(&letter)?)
+            .await
+            .map_err(Error::InternalError)?;
+        producer.flush().await.map_err(Error::InternalError)?;
+        Ok(())
+    }
+
+    async fn dead_letters(&self, topic: &str) -> anyhow::Result<Vec<DeadLetter>> {
+        let topic = self.topic(topic);
+        // Topics are only created once a letter is sent to them.
+        if !topic_exists(&self.fluvio, &topic).await? {
+            return Ok(Vec::new());
+        }
+
+        let consumer_config = ConsumerConfigExtBuilder::default()
+            .topic(topic)
+            .offset_start(Offset::beginning())
+            .disable_continuous(true)
+            .build()
+            .map_err(Error::ErrorCreatingConsumer)?;
+
+        let mut consumer_stream = self
+            .fluvio
+            .consumer_with_config(consumer_config)
+            .await
+            .map_err(Error::ErrorCreatingConsumer)?;
+
+        let mut letters = Vec::new();
+        while let Some(record) = consumer_stream.next().await {
+            letters.push(from_slice(record?.value())?);
+        }
+        Ok(letters)
+    }
+}
@@ -0,0 +1,916 @@
+use async_trait::async_trait;
+use fluvio::{
+    Compression, Fluvio, ProduceOutput, TopicProducer, TopicProducerConfig,
+    TopicProducerConfigBuilder,
+    metadata::topic::{
+        CleanupPolicy as FluvioCleanupPolicy, CompressionAlgorithm, SegmentBasedPolicy, TopicSpec,
+        TopicStorageConfig,
+    },
+    spu::SpuSocketPool,
+};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+    marker::PhantomData,
+    sync::Arc,
+    time::Duration,
+};
+use thiserror::Error;
+use tokio::{
+    sync::{OnceCell, RwLock},
+    task::JoinHandle,
+    time::{Instant, timeout_at},
+};
+use tracing::{error, warn};
+use uuid::Uuid;
+
+use crate::publisher::{
+    EventManager, EventSubscriberHdlrFn, Subscription, SubscriptionId, TypedEvent,
+    codec::{Codec, Codecs, JsonCodec},
+    dead_letter::{DeadLetter, DeadLetterSink},
+    encryption::{decrypt, encrypt},
+    envelope::Envelope,
+    keys::KeyProvider,
+    options::{AckMode, SubscribeOptions},
+    retry::RetryPolicy,
+    schema::Versioned,
+    signing::{self, sign},
+    stream::EventStream,
+    subscribers::{ReaderKey, StopSignal, Subscribers},
+    topic::{
+        Topic, TopicEvent,
+        config::{CleanupPolicy, TopicCompression, TopicConfig},
+        fluvio::{
+            dead_letter::FluvioDeadLetters,
+            reader::{ReaderState, ReaderStateCallback, TopicReader},
+            replay::{CatchUp, ReplayFrom, ReplayStream, end_offsets},
+        },
+    },
+};
+
+pub use fluvio::RecordKey;
+
+pub mod admin;
+pub mod dead_letter;
+pub mod reader;
+pub mod replay;
+
+/// Values created once per key on first use, without holding the map lock while they are created.
+type OnceMap<K, V> = RwLock<HashMap<K, Arc<OnceCell<V>>>>;
+
+type ProducerMap = OnceMap<Topic, TopicProducer<SpuSocketPool>>;
+
+async fn once_cell<K, V>(map: &OnceMap<K, V>, key: &K) -> Arc<OnceCell<V>>
+where
+    K: Clone + Eq + Hash,
+{
+    if let Some(cell) = map.read().await.get(key) {
+        return cell.clone();
+    }
+    map.write().await.entry(key.clone()).or_default().clone()
+}
+
+/// Topics this handler has made sure exist, so the admin API is asked about each one only once.
+#[derive(Default)]
+pub(crate) struct KnownTopics(OnceMap<String, ()>);
+
+impl KnownTopics {
+    pub(crate) async fn ensure(
+        &self,
+        fluvio: &Fluvio,
+        topic: &str,
+        config: &TopicConfig,
+    ) -> anyhow::Result<()> {
+        once_cell(&self.0, &topic.to_string())
+            .await
+            .get_or_try_init(|| try_create_topic(fluvio, topic, config))
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Error)]
+#[allow(clippy::enum_variant_names)]
+enum Error {
+    #[error("Error creating fluvio topic: {0}")]
+    ErrorCreatingTopic(anyhow::Error),
+    #[error("Error acquiring fluvio topics: {0}")]
+    ErrorAcquiringTopics(anyhow::Error),
+    #[error("Error connecting to fluvio: {0}")]
+    ErrorConnectingToFluvio(anyhow::Error),
+    #[error("Error creating fluvio producer: {0}")]
+    ErrorCreatingProducer(anyhow::Error),
+    #[error("Error creating fluvio consumer: {0}")]
+    ErrorCreatingConsumer(anyhow::Error),
+    #[error("Internal handler error: {0}")]
+    InternalError(anyhow::Error),
+    #[error("Handler was shut down")]
+    ShutDown,
+}
+
+pub struct FluvioHandler<T: TypedEvent> {
+    fluvio: Arc<Fluvio>,
+    subscribers: Arc<Subscribers<T>>,
+    topics: Arc<KnownTopics>,
+    producers: ProducerMap,
+    dead_letters: Arc<dyn DeadLetterSink>,
+    quarantine: Arc<dyn DeadLetterSink>,
+    source_service: Option<String>,
+    default_options: SubscribeOptions,
+    reconnect: RetryPolicy,
+    redelivery: RetryPolicy,
+    on_reader_state: Option<ReaderStateCallback>,
+    topic_configs: HashMap<Topic, TopicConfig>,
+    topic_compressions: HashMap<Topic, TopicCompression>,
+    namespace: Option<String>,
+    codecs: Arc<Codecs<T>>,
+    signing_keys: HashMap<Topic, Arc<dyn KeyProvider>>,
+    encryption_keys: Option<Arc<dyn KeyProvider>>,
+    encrypted_types: HashSet<T::EventType>,
+}
+
+/// What [`FluvioHandler::shutdown`] could not finish before its timeout.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ShutdownReport {
+    /// Topics whose readers were still handling a record and had to be aborted.
+    pub unfinished_readers: Vec<Topic>,
+    /// Topics whose producers could not flush their pending records.
+    pub unflushed_topics: Vec<Topic>,
+}
+
+impl ShutdownReport {
+    pub fn is_clean(&self) -> bool {
+        self.unfinished_readers.is_empty() && self.unflushed_topics.is_empty()
+    }
+}
+
+pub struct FluvioHandlerBuilder<T: TypedEvent> {
+    dead_letters: Option<Arc<dyn DeadLetterSink>>,
+    quarantine: Option<Arc<dyn DeadLetterSink>>,
+    source_service: Option<String>,
+    default_options: SubscribeOptions,
+    reconnect: RetryPolicy,
+    redelivery: RetryPolicy,
+    on_reader_state: Option<ReaderStateCallback>,
+    topic_configs: HashMap<Topic, TopicConfig>,
+    topic_compressions: HashMap<Topic, TopicCompression>,
+    namespace: Option<String>,
+    codec: Option<Arc<dyn Codec<T>>>,
+    topic_codecs: HashMap<Topic, Arc<dyn Codec<T>>>,
+    signing_keys: HashMap<Topic, Arc<dyn KeyProvider>>,
+    encryption_keys: Option<Arc<dyn KeyProvider>>,
+    encrypted_types: HashSet<T::EventType>,
+    _event: PhantomData<T>,
+}
+
+impl<T: TypedEvent> FluvioHandlerBuilder<T> {
+    /// Options used by [`EventManager::subscribe`] and [`EventManager::subscribe_stream`].
+    pub fn default_subscribe_options(mut self, options: SubscribeOptions) -> Self {
+        self.default_options = options;
+        self
+    }
+
+    /// Name recorded as the `source_service` of every event this handler publishes.
+    pub fn source_service(mut self, source_service: impl Into<String>) -> Self {
+        self.source_service = Some(source_service.into());
+        self
+    }
+
+    /// Sends dead letters to `sink` instead of the `<topic>-dlq` topics.
+    pub fn dead_letter_sink(mut self, sink: Arc<dyn DeadLetterSink>) -> Self {
+        self.dead_letters = Some(sink);
+        self
+    }
+
+    /// Sends the records of signed topics that fail their signature check to `sink` instead
+    /// of the `<topic>-quarantine` topics.
+    pub fn quarantine_sink(mut self, sink: Arc<dyn DeadLetterSink>) -> Self {
+        self.quarantine = Some(sink);
+        self
+    }
+
+    /// Backoff between the reconnections of a topic reader whose stream failed, and how many
+    /// to try before the reader gives up. Retries forever by default.
+    pub fn reconnect_policy(mut self, policy: RetryPolicy) -> Self {
+        self.reconnect = policy;
+        self
+    }
+
+    /// How many times a record no listener of an [`AckMode::Manual`] subscription acknowledged
+    /// is read, and how long to wait in between, before it is dead-lettered. Five by default.
+    pub fn redelivery_policy(mut self, policy: RetryPolicy) -> Self {
+        self.redelivery = policy;
+        self
+    }
+
+    /// Calls `callback` whenever a topic reader connects, disconnects, reconnects or gives up.
+    pub fn on_reader_state(
+        mut self,
+        callback: impl Fn(Topic, &ReaderState) + Send + Sync + 'static,
+    ) -> Self {
+        self.on_reader_state = Some(Arc::new(callback));
+        self
+    }
+
+    /// Creates `topic` with `config` instead of the one its events provide.
+    pub fn topic_config(mut self, topic: Topic, config: TopicConfig) -> Self {
+        self.topic_configs.insert(topic, config);
+        self
+    }
+
+    /// Compresses the records of `topic` with `compression`, whatever its configuration
+    /// says otherwise. Readers decompress them on their own.
+    pub fn topic_compression(mut self, topic: Topic, compression: TopicCompression) -> Self {
+        self.topic_compressions.insert(topic, compression);
+        self
+    }
+
+    /// Prefixes every topic this handler creates, produces to or consumes from with
+    /// `namespace`, so `auth-signed` becomes `<namespace>.auth-signed`.
+    pub fn namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.namespace = Some(namespace.into());
+        self
+    }
+
+    /// Encodes the events this handler publishes with `codec` instead of JSON.
+    pub fn codec(mut self, codec: impl Codec<T> + 'static) -> Self {
+        self.codec = Some(Arc::new(codec));
+        self
+    }
+
+    /// Encodes the events published to `topic` with `codec`. Records are marked with the
+    /// content type of their codec, so readers decode topics holding several at once.
+    pub fn topic_codec(mut self, topic: Topic, codec: impl Codec<T> + 'static) -> Self {
+        self.topic_codecs.insert(topic, Arc::new(codec));
+        self
+    }
+
+    /// Signs the records published to `topic` with the current key of `keys`. Its readers
+    /// quarantine the records without a valid signature instead of handing them to listeners.
+    pub fn topic_signing(mut self, topic: Topic, keys: Arc<dyn KeyProvider>) -> Self {
+        self.signing_keys.insert(topic, keys);
+        self
+    }
+
+    /// Keys the events chosen with [`encrypt`](Self::encrypt) are encrypted with, and the
+    /// encrypted records this handler reads are decrypted with. Readers without them
+    /// dead-letter encrypted records as undecodable.
+    pub fn encryption_keys(mut self, keys: Arc<dyn KeyProvider>) -> Self {
+        self.encryption_keys = Some(keys);
+        self
+    }
+
+    /// Encrypts the records of the events of `event_type` with AES-256-GCM, under the current
+    /// key of the [`encryption_keys`](Self::encryption_keys).
+    pub fn encrypt(mut self, event_type: T::EventType) -> Self {
+        self.encrypted_types.insert(event_type);
+        self
+    }
+}
+
+impl<T> FluvioHandlerBuilder<T>
+where
+    T: TypedEvent + Serialize + DeserializeOwned + Versioned + 'static,
+{
+    pub async fn build(self) -> anyhow::Result<FluvioHandler<T>> {
+        if !self.encrypted_types.is_empty() && self.encryption_keys.is_none() {
+            anyhow::bail!("events to encrypt were chosen without encryption keys");
+        }
+        let fluvio = Arc::new(
+            Fluvio::connect()
+                .await
+                .map_err(Error::ErrorConnectingToFluvio)?,
+        );
+        let dead_letters = self
+            .dead_letters
+            .unwrap_or_else(|| Arc::new(FluvioDeadLetters::new(fluvio.clone())));
+        let quarantine = self
+            .quarantine
+            .unwrap_or_else(|| Arc::new(FluvioDeadLetters::quarantine(fluvio.clone())));
+
+        Ok(FluvioHandler {
+            fluvio,
+            subscribers: Default::default(),
+            topics: Default::default(),
+            producers: Default::default(),
+            dead_letters,
+            quarantine,
+            source_service: self.source_service,
+            default_options: self.default_options,
+            reconnect: self.reconnect,
+            redelivery: self.redelivery,
+            on_reader_state: self.on_reader_state,
+            topic_configs: self.topic_configs,
+            topic_compressions: self.topic_compressions,
+            namespace: self.namespace,
+            codecs: Arc::new(Codecs::new(
+                self.codec.unwrap_or_else(|| Arc::new(JsonCodec)),
+                self.topic_codecs,
+            )),
+            signing_keys: self.signing_keys,
+            encryption_keys: self.encryption_keys,
+            encrypted_types: self.encrypted_types,
+        })
+    }
+}
+
+impl<T> FluvioHandler<T>
+where
+    T: TypedEvent + Serialize + DeserializeOwned + Versioned + 'static,
+{
+    pub async fn new() -> anyhow::Result<Self> {
+        Self::builder().build().await
+    }
+}
+
+impl<T: TypedEvent> FluvioHandler<T> {
+    pub fn builder() -> FluvioHandlerBuilder<T> {
+        FluvioHandlerBuilder {
+            dead_letters: None,
+            quarantine: None,
+            source_service: None,
+            default_options: SubscribeOptions::default(),
+            reconnect: RetryPolicy::exponential(u32::MAX),
+            redelivery: RetryPolicy::exponential(5),
+            on_reader_state: None,
+            topic_configs: HashMap::new(),
+            topic_compressions: HashMap::new(),
+            namespace: None,
+            codec: None,
+            topic_codecs: HashMap::new(),
+            signing_keys: HashMap::new(),
+            encryption_keys: None,
+            encrypted_types: HashSet::new(),
+            _event: PhantomData,
+        }
+    }
+
+    /// Name of `topic` on the cluster.
+    pub fn topic_name(&self, topic: &str) -> String {
+        namespaced(self.namespace.as_deref(), topic)
+    }
+
+    fn topic_config(&self, event: &impl TopicEvent) -> TopicConfig {
+        let topic = event.event_topic();
+        let config = self
+            .topic_configs
+            .get(topic)
+            .cloned()
+            .unwrap_or_else(|| event.topic_config());
+        match self.topic_compressions.get(topic) {
+            Some(compression) => config.with_compression(*compression),
+            None => config,
+        }
+    }
+
+    /// Lists the dead letters recorded for `topic`.
+    pub async fn dead_letters(&self, topic: &str) -> anyhow::Result<Vec<DeadLetter>> {
+        self.dead_letters
+            .dead_letters(&self.topic_name(topic))
+            .await
+    }
+
+    /// Lists the records of `topic` quarantined for failing their signature check.
+    pub async fn quarantined(&self, topic: &str) -> anyhow::Result<Vec<DeadLetter>> {
+        self.quarantine.dead_letters(&self.topic_name(topic)).await
+    }
+
+    /// Deletes every topic of this handler's namespace, or every topic when it has none.
+    #[cfg(test)]
+    pub(crate) async fn reset_fluvio(&self) -> anyhow::Result<()> {
+        let admin = self.fluvio.admin().await;
+
+        let topics = admin
+            .all::<TopicSpec>()
+            .await
+            .map_err(Error::ErrorAcquiringTopics)?;
+
+        let prefix = self.topic_name("");
+        for topic in topics {
+            let name = topic.name;
+            if !name.starts_with(&prefix) {
+                continue;
+            }
+            tracing::debug!("Deleting topic: {}", name);
+            admin.delete::<TopicSpec>(&name).await?;
+        }
+
+        Ok(())
+    }
+
+    #[cfg(test)]
+    pub(crate) async fn produce_raw(&self, topic: &str, value: &[u8]) -> anyhow::Result<()> {
+        let topic = self.topic_name(topic);
+        let topic = topic.as_str();
+        self.topics
+            .ensure(&self.fluvio, topic, &TopicConfig::default())
+            .await?;
+        let producer = self.fluvio.topic_producer(topic).await?;
+        producer.send(RecordKey::NULL, value).await?;
+        producer.flush().await?;
+        Ok(())
+    }
+
+    /// Spec `topic` was created with.
+    #[cfg(test)]
+    pub(crate) async fn created_spec(&self, topic: &str) -> anyhow::Result<TopicSpec> {
+        let name = self.topic_name(topic);
+        self.fluvio
+            .admin()
+            .await
+            .all::<TopicSpec>()
+            .await
+            .map_err(Error::ErrorAcquiringTopics)?
+            .into_iter()
+            .find(|existing| existing.name == name)
+            .map(|existing| existing.spec)
+            .ok_or_else(|| anyhow::anyhow!("topic {name} was not created"))
+    }
+
+    /// Every record of `topic`, in the order they were published.
+    #[cfg(test)]
+    pub(crate) async fn records(
+        &self,
+        topic: &str,
+    ) -> anyhow::Result<Vec<fluvio::consumer::Record>> {
+        use fluvio::{Offset, consumer::ConsumerConfigExtBuilder};
+        use tokio_stream::StreamExt;
+
+        let consumer_config = ConsumerConfigExtBuilder::default()
+            .topic(self.topic_name(topic))
+            .offset_start(Offset::beginning())
+            .disable_continuous(true)
+            .build()?;
+        let mut consumer_stream = self.fluvio.consumer_with_config(consumer_config).await?;
+
+        let mut records = Vec::new();
+        while let Some(record) = consumer_stream.next().await {
+            records.push(record?);
+        }
+        Ok(records)
+    }
+
+    /// Keys of every record of `topic`, in the order they were published.
+    #[cfg(test)]
+    pub(crate) async fn record_keys(&self, topic: &str) -> anyhow::Result<Vec<Option<Vec<u8>>>> {
+        Ok(self
+            .records(topic)
+            .await?
+            .iter()
+            .map(|record| record.key().map(<[u8]>::to_vec))
+            .collect())
+    }
+}
+
+impl<T> FluvioHandler<T>
+where
+    T: TypedEvent + Clone + for<'a> Deserialize<'a> + Send + Sync + 'static,
+{
+    /// Stops every topic reader from pulling new records, waits for the handlers already running
+    /// and flushes the producers, giving up on whatever is left once `timeout` has passed.
+    ///
+    /// Every subscription is dropped, and the handler refuses to subscribe or publish from then
+    /// on. Use [`flush`](Self::flush) to wait for published events while keeping it running.
+    pub async fn shutdown(&self, timeout: Duration) -> ShutdownReport {
+        let deadline = Instant::now() + timeout;
+        let mut report = ShutdownReport::default();
+
+        for (key, mut handle) in self.subscribers.close().await {
+            if timeout_at(deadline, &mut handle).await.is_err() {
+                warn!(
+                    "Reader of topic {} did not stop in time, aborting",
+                    key.topic
+                );
+                handle.abort();
+                report.unfinished_readers.push(key.topic);
+            }
+        }
+
+        let producers = self.producers.write().await.drain().collect::<Vec<_>>();
+        for (topic, producer) in producers {
+            let Some(producer) = producer.get() else {
+                continue;
+            };
+            match timeout_at(deadline, producer.flush()).await {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => {
+                    error!("Error flushing producer of topic {}: {}", topic, e);
+                    report.unflushed_topics.push(topic);
+                }
+                Err(_) => {
+                    warn!("Producer of topic {} did not flush in time", topic);
+                    report.unflushed_topics.push(topic);
+                }
+            }
+        }
+
+        report
+    }
+
+    /// Waits until every event published so far has been sent.
+    pub async fn flush(&self) -> anyhow::Result<()> {
+        let producers = self
+            .producers
+            .read()
+            .await
+            .iter()
+            .map(|(topic, producer)| (*topic, producer.clone()))
+            .collect::<Vec<_>>();
+        for (topic, producer) in producers {
+            if let Some(producer) = producer.get() {
+                producer.flush().await.map_err(|e| {
+                    Error::InternalError(anyhow::anyhow!(
+                        "Error flushing producer of topic {}: {}",
+                        topic,
+                        e
+                    ))
+                })?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Streams the events of `event_type` from `from` onwards, through a reader of its own,
+    /// signalling when the events published before the call have all been read.
+    pub async fn subscribe_from(
+        &self,
+        event_type: T::EventType,
+        from: ReplayFrom,
+    ) -> anyhow::Result<ReplayStream<T>>
+    where
+        T::EventType: TopicEvent,
+    {
+        let topic = event_type.event_topic();
+        let name = self.topic_name(topic);
+        let config = self.topic_config(&event_type);
+        self.topics.ensure(&self.fluvio, &name, &config).await?;
+
+        // A consumer name of its own keeps the replay from sharing a reader that is past `from`.
+        let options = self
+            .default_options
+            .clone()
+            .with_start(from.into())
+            .with_consumer_name(format!("replay-{}", Uuid::new_v4()))
+            .with_ack(AckMode::None);
+        let offsets = end_offsets(&self.fluvio, &name, &options.consumer.partitions).await?;
+        let (catch_up, caught_up) = CatchUp::new(offsets);
+
+        let (listener, events) = EventStream::channel();
+        let subscription = self
+            .subscribers
+            .subscribe(event_type, topic, listener, options, async |key, stop| {
+                self.topic_reader(key, config, Some(catch_up), stop).await
+            })
+            .await?;
+        Ok(ReplayStream::new(
+            EventStream::new(events, subscription),
+            caught_up,
+        ))
+    }
+
+    async fn topic_reader(
+        &self,
+        key: &ReaderKey,
+        config: TopicConfig,
+        catch_up: Option<CatchUp>,
+        stop: StopSignal,
+    ) -> anyhow::Result<JoinHandle<()>> {
+        let reader = TopicReader {
+            fluvio: self.fluvio.clone(),
+            topics: self.topics.clone(),
+            name: self.topic_name(key.topic),
+            config,
+            subscribers: self.subscribers.clone(),
+            dead_letters: self.dead_letters.clone(),
+            quarantine: self.quarantine.clone(),
+            reconnect: self.reconnect.clone(),
+            redelivery: self.redelivery.clone(),
+            on_state: self.on_reader_state.clone(),
+            catch_up,
+            codecs: self.codecs.clone(),
+            signing_keys: self.signing_keys.get(key.topic).cloned(),
+            encryption_keys: self.encryption_keys.clone(),
+        };
+        reader
+            .spawn(key, stop)
+            .await
+            .map_err(|e| Error::InternalError(e).into())
+    }
+}
+
+impl<T> FluvioHandler<T>
+where
+    T: TypedEvent + TopicEvent + 'static,
+{
+    /// Publishes the raw payload of `letter` back to the topic it was read from, under the
+    /// key it was published with, so it stays in order with the other events of its aggregate.
+    pub async fn redrive(&self, letter: &DeadLetter) -> anyhow::Result<()> {
+        // The event tells which topic, and so which producer, the payload goes back through.
+        let value = decrypt(
+            self.encryption_keys.as_deref(),
+            signing::unsigned(&letter.payload)?,
+        )?;
+        let event = self.codecs.decode(&value)?.event;
+        let topic = event.event_topic();
+        if self.topic_name(topic) != letter.topic {
+            anyhow::bail!(
+                "dead letter of topic {} holds an event of topic {}",
+                letter.topic,
+                topic
+            );
+        }
+
+        let key = letter.key.clone().map_or(RecordKey::NULL, RecordKey::from);
+        self.produce(&event, key, letter.payload.clone())
+            .await?
+            .wait()
+            .await
+            .map_err(|e| Error::InternalError(e.into()))?;
+        Ok(())
+    }
+
+    /// Sends `value` with the producer of the topic of `event`, which is created on first use.
+    async fn produce(
+        &self,
+        event: &T,
+        key: RecordKey,
+        value: Vec<u8>,
+    ) -> anyhow::Result<ProduceOutput> {
+        if self.subscribers.is_closed() {
+            return Err(Error::ShutDown.into());
+        }
+        let topic = event.event_topic();
+        let producer = once_cell(&self.producers, &topic).await;
+        let producer = producer
+            .get_or_try_init(|| async {
+                let name = self.topic_name(topic);
+                let config = self.topic_config(event);
+                self.topics.ensure(&self.fluvio, &name, &config).await?;
+                let producer = self
+                    .fluvio
+                    .topic_producer_with_config(name, producer_config(&config)?)
+                    .await
+                    .map_err(Error::ErrorCreatingProducer)?;
+                anyhow::Ok(producer)
+            })
+            .await?;
+        Ok(producer
+            .send(key, value)
+            .await
+            .map_err(Error::InternalError)?)
+    }
+}
+
+/// Partition key of an event. Events with the same key land on the same partition,
+/// so they are read in the order they were published.
+pub trait KeyEvent {
+    fn event_key(&self) -> RecordKey;
+}
+
+#[async_trait]
+impl<T> EventManager for FluvioHandler<T>
+where
+    T: TypedEvent
+        + TopicEvent
+        + KeyEvent
+        + Clone
+        + for<'a> Deserialize<'a>
+        + Serialize
+        + Versioned
+        + Send
+        + Sync
+        + 'static,
+    T::EventType: TopicEvent,
+{
+    type Event = T;
+
+    async fn subscribe(
+        &self,
+        event: Self::Event,
+        listener: EventSubscriberHdlrFn<Self::Event>,
+    ) -> anyhow::Result<Subscription> {
+        self.subscribe_with(event, listener, self.default_options.clone())
+            .await
+    }
+
+    async fn subscribe_with(
+        &self,
+        event: Self::Event,
+        listener: EventSubscriberHdlrFn<Self::Event>,
+        options: SubscribeOptions,
+    ) -> anyhow::Result<Subscription> {
+        let topic = event.event_topic();
+        let config = self.topic_config(&event);
+        self.subscribers
+            .subscribe(
+                event.event_type(),
+                topic,
+                listener,
+                options,
+                async |key, stop| self.topic_reader(key, config, None, stop).await,
+            )
+            .await
+    }
+
+    async fn subscribe_stream(
+        &self,
+        event_type: <Self::Event as TypedEvent>::EventType,
+    ) -> anyhow::Result<EventStream<Self::Event>> {
+        let topic = event_type.event_topic();
+        let config = self.topic_config(&event_type);
+        let (listener, events) = EventStream::channel();
+        let subscription = self
+            .subscribers
+            .subscribe(
+                event_type,
+                topic,
+                listener,
+                self.default_options.clone(),
+                async |key, stop| self.topic_reader(key, config, None, stop).await,
+            )
+            .await?;
+        Ok(EventStream::new(events, subscription))
+    }
+
+    async fn unsubscribe(&self, subscription: SubscriptionId) -> anyhow::Result<()> {
+        self.subscribers.unsubscribe(subscription).await;
+        Ok(())
+    }
+
+    async fn notify_envelope(&self, mut envelope: Envelope<Self::Event>) -> anyhow::Result<()> {
+        if envelope.metadata.source_service.is_none() {
+            envelope.metadata.source_service = self.source_service.clone();
+        }
+
+        let event = &envelope.event;
+        let topic = event.event_topic();
+        let key = match &envelope.partition_key {
+            Some(key) => RecordKey::from(key.clone()),
+            None => event.event_key(),
+        };
+        let mut value = self.codecs.encode(topic, &envelope)?;
+        if let Some(keys) = &self.encryption_keys
+            && self.encrypted_types.contains(&event.event_type())
+        {
+            value = encrypt(keys.as_ref(), &value)?;
+        }
+        if let Some(keys) = self.signing_keys.get(topic) {
+            value = sign(keys.as_ref(), &value)?;
+        }
+        self.produce(event, key, value).await?;
+        Ok(())
+    }
+}
+
+pub(crate) fn namespaced(namespace: Option<&str>, topic: &str) -> String {
+    match namespace {
+        Some(namespace) => format!("{namespace}.{topic}"),
+        None => topic.to_string(),
+    }
+}
+
+async fn topic_exists(fluvio: &Fluvio, topic: &str) -> anyhow::Result<bool> {
+    let topics = fluvio
+        .admin()
+        .await
+        .all::<TopicSpec>()
+        .await
+        .map_err(Error::ErrorAcquiringTopics)?;
+    Ok(topics.iter().any(|existing| existing.name == topic))
+}
+
+async fn try_create_topic(
+    fluvio: &Fluvio,
+    topic: &str,
+    config: &TopicConfig,
+) -> anyhow::Result<()> {
+    let admin = fluvio.admin().await;
+
+    let topics = admin
+        .all::<TopicSpec>()
+        .await
+        .map_err(Error::ErrorAcquiringTopics)?;
+
+    match topics.iter().find(|existing| existing.name == topic) {
+        Some(existing) => {
+            for mismatch in config_mismatches(config, &existing.spec) {
+                warn!(
+                    "Topic {} does not match its configuration: {}",
+                    topic, mismatch
+                );
+            }
+        }
+        None => admin
+            .create(topic.to_string(), false, topic_spec(config))
+            .await
+            .map_err(Error::ErrorCreatingTopic)?,
+    }
+
+    Ok(())
+}
+
+pub(crate) fn topic_spec(config: &TopicConfig) -> TopicSpec {
+    let mut spec = TopicSpec::new_computed(config.partitions, config.replication_factor, None);
+    spec.set_compression_type(compression_algorithm(config.compression));
+    if let Some(retention) = config.retention {
+        spec.set_cleanup_policy(cleanup_policy(config.cleanup_policy, retention));
+    }
+    if let Some(segment_size) = config.max_segment_size {
+        spec.set_storage(TopicStorageConfig {
+            segment_size: Some(segment_size),
+            ..Default::default()
+        });
+    }
+    spec
+}
+
+/// Describes every setting of `spec` that differs from `config`.
+pub(crate) fn config_mismatches(config: &TopicConfig, spec: &TopicSpec) -> Vec<String> {
+    let mut mismatches = Vec::new();
+    let replicas = spec.replicas();
+    if replicas.partitions() != config.partitions {
+        mismatches.push(format!(
+            "{} partitions instead of {}",
+            replicas.partitions(),
+            config.partitions
+        ));
+    }
+    if let Some(replication_factor) = replicas.replication_factor()
+        && replication_factor != config.replication_factor
+    {
+        mismatches.push(format!(
+            "replication factor {} instead of {}",
+            replication_factor, config.replication_factor
+        ));
+    }
+    if let Some(retention) = config.retention {
+        let expected = cleanup_policy(config.cleanup_policy, retention);
+        if spec.get_clean_policy() != Some(&expected) {
+            mismatches.push(format!(
+                "cleanup policy {:?} instead of {:?}",
+                spec.get_clean_policy(),
+                expected
+            ));
+        }
+    }
+    if let Some(segment_size) = config.max_segment_size {
+        let actual = spec.get_storage().and_then(|storage| storage.segment_size);
+        if actual != Some(segment_size) {
+            mismatches.push(format!(
+                "segment size {:?} instead of {}",
+                actual, segment_size
+            ));
+        }
+    }
+    let compression = compression_algorithm(config.compression);
+    if *spec.get_compression_type() != compression {
+        mismatches.push(format!(
+            "compression {:?} instead of {:?}",
+            spec.get_compression_type(),
+            compression
+        ));
+    }
+    mismatches
+}
+
+/// Producers compress their batches as their topic is configured to, which readers undo on
+/// their own. Setting it on the producer covers topics created to accept any compression.
+pub(crate) fn producer_config(config: &TopicConfig) -> anyhow::Result<TopicProducerConfig> {
+    let mut builder = TopicProducerConfigBuilder::default();
+    if let Some(compression) = producer_compression(config.compression) {
+        builder.compression(compression);
+    }
+    Ok(builder
+        .build()
+        .map_err(|e| Error::ErrorCreatingProducer(e.into()))?)
+}
+
+fn producer_compression(compression: TopicCompression) -> Option<Compression> {
+    match compression {
+        TopicCompression::Any | TopicCompression::None => None,
+        TopicCompression::Gzip => Some(Compression::Gzip),
+        TopicCompression::Snappy => Some(Compression::Snappy),
+        TopicCompression::Lz4 => Some(Compression::Lz4),
+        TopicCompression::Zstd => Some(Compression::Zstd),
+    }
+}
+
+fn compression_algorithm(compression: TopicCompression) -> CompressionAlgorithm {
+    match compression {
+        TopicCompression::Any => CompressionAlgorithm::Any,
+        TopicCompression::None => CompressionAlgorithm::None,
+        TopicCompression::Gzip => CompressionAlgorithm::Gzip,
+        TopicCompression::Snappy => CompressionAlgorithm::Snappy,
+        TopicCompression::Lz4 => CompressionAlgorithm::Lz4,
+        TopicCompression::Zstd => CompressionAlgorithm::Zstd,
+    }
+}
+
+fn cleanup_policy(policy: CleanupPolicy, retention: Duration) -> FluvioCleanupPolicy {
+    match policy {
+        CleanupPolicy::Delete => FluvioCleanupPolicy::Segment(SegmentBasedPolicy {
+            time_in_seconds: retention.as_secs().try_into().unwrap_or(u32::MAX),
+        }),
+    }
+}
@@ -0,0 +1,577 @@
+use fluvio::{
+    Fluvio, Offset,
+    consumer::{
+        ConsumerBoxFuture, ConsumerConfigExt, ConsumerConfigExtBuilder, ConsumerStream,
+        OffsetManagementStrategy, Record, RetryMode,
+    },
+    dataplane::link::ErrorCode,
+    metadata::topic::TopicSpec,
+};
+use futures_util::FutureExt;
+use serde::Deserialize;
+use std::{
+    collections::HashMap,
+    panic::AssertUnwindSafe,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+use tokio::{task::JoinHandle, time::sleep};
+use tokio_stream::{Stream, StreamExt, StreamMap};
+use tracing::{error, info, warn};
+
+use crate::publisher::{
+    TypedEvent, ack,
+    codec::Codecs,
+    dead_letter::{DeadLetter, DeadLetterReason, DeadLetterSink},
+    encryption::decrypt,
+    envelope::Envelope,
+    keys::KeyProvider,
+    now_millis,
+    options::{AckMode, ConsumerOptions, StartOffset},
+    retry::RetryPolicy,
+    signing,
+    subscribers::{ReaderKey, StopSignal, Subscribers},
+    topic::{
+        Topic,
+        config::TopicConfig,
+        fluvio::{
+            Error, KnownTopics,
+            replay::{CatchUp, end_offsets},
+        },
+    },
+};
+
+type RecordStream = Box<dyn ConsumerStream + Send>;
+
+pub type ReaderStateCallback = Arc<dyn Fn(Topic, &ReaderState) + Send + Sync>;
+
+/// Connection state of a topic reader, reported to the callback set with
+/// [`FluvioHandlerBuilder::on_reader_state`](super::FluvioHandlerBuilder::on_reader_state).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReaderState {
+    /// The reader is connected and pulling records.
+    Connected,
+    /// The stream failed or ended, or a record has to be read again, and the reader is
+    /// about to reconnect.
+    Disconnected { error: String },
+    /// The reader is connecting again after `attempt` failed reconnections.
+    Reconnecting { attempt: u32 },
+    /// The reconnect policy ran out of attempts, or the reader failed, and it stopped for good.
+    GaveUp,
+}
+
+/// Everything a topic reader task needs to pull records and hand them to the listeners.
+pub(super) struct TopicReader<T: TypedEvent> {
+    pub(super) fluvio: Arc<Fluvio>,
+    pub(super) topics: Arc<KnownTopics>,
+    /// Name of the topic on the cluster, with the handler's namespace.
+    pub(super) name: String,
+    pub(super) config: TopicConfig,
+    pub(super) subscribers: Arc<Subscribers<T>>,
+    pub(super) dead_letters: Arc<dyn DeadLetterSink>,
+    pub(super) quarantine: Arc<dyn DeadLetterSink>,
+    pub(super) reconnect: RetryPolicy,
+    /// How many times a record no listener acknowledged is read again before it is
+    /// dead-lettered, and how long to wait before each time.
+    pub(super) redelivery: RetryPolicy,
+    pub(super) on_state: Option<ReaderStateCallback>,
+    /// Set for replays, to tell when the reader caught up with the topic.
+    pub(super) catch_up: Option<CatchUp>,
+    pub(super) codecs: Arc<Codecs<T>>,
+    /// Set for signed topics, whose records are only handled once their signature checks out.
+    pub(super) signing_keys: Option<Arc<dyn KeyProvider>>,
+    pub(super) encryption_keys: Option<Arc<dyn KeyProvider>>,
+}
+
+impl<T> TopicReader<T>
+where
+    T: TypedEvent + Clone + for<'a> Deserialize<'a> + Send + Sync + 'static,
+{
+    /// Connects to the topic of `key` and spawns the task reading from it.
+    pub(super) async fn spawn(
+        self,
+        key: &ReaderKey,
+        stop: StopSignal,
+    ) -> anyhow::Result<JoinHandle<()>> {
+        let key = key.clone();
+        self.topics
+            .ensure(&self.fluvio, &self.name, &self.config)
+            .await?;
+        let next_offsets = self.start_offsets(&key.consumer).await?;
+        let consumer_stream = self.connect(&key, &next_offsets).await?;
+        self.report(key.topic, ReaderState::Connected);
+
+        let on_state = self.on_state.clone();
+        Ok(tokio::spawn(async move {
+            let topic = key.topic;
+            let run = self.run(key, next_offsets, consumer_stream, stop);
+            // A reader that panics stops for good, which its subscribers are told like any other.
+            if AssertUnwindSafe(run).catch_unwind().await.is_err() {
+                error!("Reader of topic {} panicked", topic);
+                if let Some(on_state) = on_state {
+                    on_state(topic, &ReaderState::GaveUp);
+                }
+            }
+        }))
+    }
+
+    /// Connects to every partition of the topic, each from its next offset if it has one and
+    /// from the start of the reader otherwise.
+    async fn connect(
+        &self,
+        key: &ReaderKey,
+        next_offsets: &HashMap<u32, i64>,
+    ) -> anyhow::Result<RecordStream> {
+        let mut streams = StreamMap::new();
+        for partition in self.partitions(&key.consumer).await? {
+            let offset = match next_offsets.get(&partition) {
+                Some(next) => Offset::absolute(*next)?,
+                None => start_offset(key.consumer.start)?,
+            };
+            let config = consumer_config(&self.name, &key.consumer, partition, offset)?;
+            let consumer_stream = self
+                .fluvio
+                .consumer_with_config(config)
+                .await
+                .map_err(Error::ErrorCreatingConsumer)?;
+            streams.insert(partition, Box::new(consumer_stream) as RecordStream);
+        }
+        #[cfg(test)]
+        streams.insert(u32::MAX, disconnect_stream());
+        Ok(Box::new(PartitionStreams(streams)))
+    }
+
+    /// Partitions the reader chose, or every partition of the topic.
+    async fn partitions(&self, options: &ConsumerOptions) -> anyhow::Result<Vec<u32>> {
+        if !options.partitions.is_empty() {
+            return Ok(options.partitions.clone());
+        }
+        let topics = self
+            .fluvio
+            .admin()
+            .await
+            .all::<TopicSpec>()
+            .await
+            .map_err(Error::ErrorAcquiringTopics)?;
+        let topic = topics
+            .iter()
+            .find(|topic| topic.name == self.name)
+            .ok_or_else(|| anyhow::anyhow!("topic {} does not exist", self.name))?;
+        Ok((0..topic.spec.partitions()).collect())
+    }
+
+    /// Offsets the reader starts each partition at, pinned before it first connects so a
+    /// reconnection still reads what was published while it was disconnected. Consumers that
+    /// commit offsets resume after their committed one, whatever their start.
+    async fn start_offsets(&self, options: &ConsumerOptions) -> anyhow::Result<HashMap<u32, i64>> {
+        let mut offsets = match options.start {
+            StartOffset::End => end_offsets(&self.fluvio, &self.name, &options.partitions).await?,
+            StartOffset::FromEnd(before_end) => {
+                end_offsets(&self.fluvio, &self.name, &options.partitions)
+                    .await?
+                    .into_iter()
+                    .map(|(partition, offset)| (partition, (offset - i64::from(before_end)).max(0)))
+                    .collect()
+            }
+            // Partitions left out, like those that were empty, are read from the start.
+            StartOffset::Beginning | StartOffset::Absolute(_) | StartOffset::Timestamp(_) => {
+                HashMap::new()
+            }
+        };
+        if options.ack == AckMode::None {
+            return Ok(offsets);
+        }
+
+        let committed = self
+            .fluvio
+            .consumer_offsets()
+            .await
+            .map_err(Error::ErrorCreatingConsumer)?;
+        for committed in committed {
+            if committed.consumer_id == options.consumer_name && committed.topic == self.name {
+                offsets.insert(committed.partition, committed.offset + 1);
+            }
+        }
+        Ok(offsets)
+    }
+
+    /// Reads records until stopped. `next_offsets` holds the next offset to handle on each
+    /// partition, so a reconnection neither skips nor repeats records.
+    async fn run(
+        mut self,
+        key: ReaderKey,
+        mut next_offsets: HashMap<u32, i64>,
+        mut consumer_stream: RecordStream,
+        mut stop: StopSignal,
+    ) {
+        // Deliveries so far of the record each partition is stuck on.
+        let mut deliveries = HashMap::<u32, (i64, u32)>::new();
+        loop {
+            let next = tokio::select! {
+                biased;
+                _ = stop.stopped() => return,
+                next = consumer_stream.next() => next,
+            };
+            let error = match next {
+                Some(Ok(record)) => {
+                    let (partition, offset) = (record.partition(), record.offset());
+                    if next_offsets
+                        .get(&partition)
+                        .is_some_and(|next| offset < *next)
+                    {
+                        continue;
+                    }
+                    let delivery = match deliveries.get(&partition) {
+                        Some((stuck, delivery)) if *stuck == offset => delivery + 1,
+                        _ => 1,
+                    };
+                    if !published_before(&key.consumer.start, &record)
+                        && !self
+                            .process(&key, &mut consumer_stream, &record, delivery)
+                            .await
+                    {
+                        // Committing a later record would commit this one too, so the reader
+                        // connects again to read it once more instead.
+                        deliveries.insert(partition, (offset, delivery));
+                        next_offsets.insert(partition, offset);
+                        tokio::select! {
+                            biased;
+                            _ = stop.stopped() => return,
+                            _ = sleep(self.redelivery.backoff(delivery)) => {}
+                        }
+                        format!("record {offset} of partition {partition} was not handled")
+                    } else {
+                        deliveries.remove(&partition);
+                        next_offsets.insert(partition, offset + 1);
+                        if let Some(catch_up) = &mut self.catch_up {
+                            catch_up.advance(partition, offset + 1);
+                        }
+                        continue;
+                    }
+                }
+                Some(Err(e)) => e.to_string(),
+                None => String::from("stream ended"),
+            };
+
+            self.report(key.topic, ReaderState::Disconnected { error });
+            match self.reconnect(&key, &next_offsets, &mut stop).await {
+                Some(reconnected) => consumer_stream = reconnected,
+                None => return,
+            }
+        }
+    }
+
+    /// Handles the `delivery`th delivery of `record`, committing its offset once it is done
+    /// with. Returns whether the reader may move past it, which readers that commit offsets
+    /// only do once it was handled.
+    async fn process(
+        &self,
+        key: &ReaderKey,
+        consumer_stream: &mut RecordStream,
+        record: &Record,
+        delivery: u32,
+    ) -> bool {
+        let handled = match self.handle(key, record).await {
+            Handled::Done => true,
+            Handled::Failed => false,
+            Handled::Unacknowledged if delivery < self.redelivery.max_attempts => false,
+            Handled::Unacknowledged => {
+                warn!(
+                    "Dead-lettering record {} of topic {}, unacknowledged after {} deliveries",
+                    record.offset(),
+                    key.topic,
+                    delivery
+                );
+                let error = anyhow::anyhow!("no listener acknowledged it in {delivery} deliveries");
+                let letter = self.dead_letter(record, DeadLetterReason::Unacknowledged, error);
+                send_dead_letter(&self.dead_letters, letter).await
+            }
+        };
+        if key.consumer.ack == AckMode::None {
+            return true;
+        }
+        if handled && let Err(e) = commit(consumer_stream.as_mut()).await {
+            error!(
+                "Error committing offset {} of topic {}: {}",
+                record.offset(),
+                key.topic,
+                e
+            );
+        }
+        handled
+    }
+
+    /// Connects again until it works, the reconnect policy gives up, or the reader is stopped.
+    async fn reconnect(
+        &self,
+        key: &ReaderKey,
+        next_offsets: &HashMap<u32, i64>,
+        stop: &mut StopSignal,
+    ) -> Option<RecordStream> {
+        let mut attempt = 1;
+        loop {
+            let backoff = self.reconnect.backoff(attempt);
+            tokio::select! {
+                biased;
+                _ = stop.stopped() => return None,
+                _ = sleep(backoff) => {}
+            }
+
+            self.report(key.topic, ReaderState::Reconnecting { attempt });
+            match self.connect(key, next_offsets).await {
+                Ok(consumer_stream) => {
+                    self.report(key.topic, ReaderState::Connected);
+                    return Some(consumer_stream);
+                }
+                Err(e) if attempt < self.reconnect.max_attempts => {
+                    warn!(
+                        "Reconnection {} of the reader of topic {} failed: {}",
+                        attempt, key.topic, e
+                    );
+                    attempt += 1;
+                }
+                Err(e) => {
+                    error!("Reader of topic {} gave up reconnecting: {}", key.topic, e);
+                    self.report(key.topic, ReaderState::GaveUp);
+                    return None;
+                }
+            }
+        }
+    }
+
+    /// Hands `record` to the listeners, dead-lettering it if it cannot be or a listener fails.
+    async fn handle(&self, key: &ReaderKey, record: &Record) -> Handled {
+        let value = match &self.signing_keys {
+            Some(keys) => signing::verify(keys.as_ref(), record.value()),
+            None => signing::unsigned(record.value()),
+        };
+        let value = match value {
+            Ok(value) => value,
+            Err(e) => {
+                warn!(
+                    "Quarantining record {} of topic {}: {}",
+                    record.offset(),
+                    key.topic,
+                    e
+                );
+                let letter = self.dead_letter(record, DeadLetterReason::Unverified, e.into());
+                return Handled::dead_lettered(send_dead_letter(&self.quarantine, letter).await);
+            }
+        };
+
+        let envelope = decrypt(self.encryption_keys.as_deref(), value)
+            .and_then(|value| self.codecs.decode(&value));
+        match envelope {
+            Ok(Envelope {
+                metadata, event, ..
+            }) => {
+                let dispatch = metadata.scope(self.subscribers.dispatch(key, event));
+                let (failures, acknowledged) = ack::track(dispatch).await;
+
+                // A record some listener failed on is done with once it is dead-lettered, as
+                // reading it again would run the failed listener again too.
+                if !failures.is_empty() {
+                    let mut stored = true;
+                    for (_, e) in failures {
+                        let letter = self.dead_letter(record, DeadLetterReason::ListenerFailed, e);
+                        stored &= send_dead_letter(&self.dead_letters, letter).await;
+                    }
+                    Handled::dead_lettered(stored)
+                } else if key.consumer.ack == AckMode::Manual && !acknowledged {
+                    Handled::Unacknowledged
+                } else {
+                    Handled::Done
+                }
+            }
+            Err(e) => {
+                error!("Error parsing event: {}", e);
+                let letter = self.dead_letter(record, DeadLetterReason::Undecodable, e);
+                Handled::dead_lettered(send_dead_letter(&self.dead_letters, letter).await)
+            }
+        }
+    }
+
+    fn dead_letter(
+        &self,
+        record: &Record,
+        reason: DeadLetterReason,
+        error: anyhow::Error,
+    ) -> DeadLetter {
+        DeadLetter {
+            topic: self.name.clone(),
+            partition: record.partition(),
+            offset: record.offset(),
+            timestamp: now_millis(),
+            reason,
+            error: error.to_string(),
+            key: record.key().map(<[u8]>::to_vec),
+            payload: record.value().to_vec(),
+        }
+    }
+
+    fn report(&self, topic: Topic, state: ReaderState) {
+        match &state {
+            ReaderState::Connected => info!("Reader of topic {} connected", topic),
+            ReaderState::Disconnected { error } => {
+                warn!("Reader of topic {} disconnected: {}", topic, error)
+            }
+            ReaderState::Reconnecting { attempt } => {
+                info!(
+                    "Reader of topic {} reconnecting, attempt {}",
+                    topic, attempt
+                )
+            }
+            ReaderState::GaveUp => {}
+        }
+        if let Some(on_state) = &self.on_state {
+            on_state(topic, &state);
+        }
+    }
+}
+
+/// What became of a record handed to the listeners.
+enum Handled {
+    /// Every listener handled it, or it was dead-lettered.
+    Done,
+    /// Every listener handled it, but none acknowledged it as [`AckMode::Manual`] asks.
+    Unacknowledged,
+    /// It had to be dead-lettered, which failed.
+    Failed,
+}
+
+impl Handled {
+    fn dead_lettered(stored: bool) -> Self {
+        if stored { Self::Done } else { Self::Failed }
+    }
+}
+
+/// Whether `record` was published before a [`StartOffset::Timestamp`] the reader starts from.
+fn published_before(start: &StartOffset, record: &Record) -> bool {
+    matches!(start, StartOffset::Timestamp(timestamp) if record.timestamp() < *timestamp)
+}
+
+/// Offset a partition without a next offset is read from. Readers starting from the end
+/// have one for each partition that was not empty when they started, and consumers that
+/// commit offsets one for each partition they committed on.
+fn start_offset(start: StartOffset) -> anyhow::Result<Offset> {
+    Ok(match start {
+        StartOffset::Absolute(offset) => Offset::absolute(offset)?,
+        StartOffset::Beginning
+        | StartOffset::Timestamp(_)
+        | StartOffset::End
+        | StartOffset::FromEnd(_) => Offset::beginning(),
+    })
+}
+
+/// Builds the consumer of one partition of a reader, starting at `offset`.
+fn consumer_config(
+    topic: &str,
+    options: &ConsumerOptions,
+    partition: u32,
+    offset: Offset,
+) -> anyhow::Result<ConsumerConfigExt> {
+    let offset_strategy = match options.ack {
+        AckMode::None => OffsetManagementStrategy::None,
+        AckMode::AfterHandle | AckMode::Manual => OffsetManagementStrategy::Manual,
+    };
+
+    let mut builder = ConsumerConfigExtBuilder::default();
+    builder
+        .topic(topic)
+        .partition(partition)
+        .offset_start(offset)
+        .offset_consumer(options.consumer_name.clone())
+        .offset_strategy(offset_strategy)
+        // Reconnections are ours to make, so they show up in the reader state.
+        .retry_mode(RetryMode::Disabled);
+    if let Some(max_bytes) = options.max_bytes {
+        builder.max_bytes(max_bytes);
+    }
+    Ok(builder.build().map_err(Error::ErrorCreatingConsumer)?)
+}
+
+/// The consumers of every partition of a reader, read as one stream.
+struct PartitionStreams(StreamMap<u32, RecordStream>);
+
+impl Stream for PartitionStreams {
+    type Item = Result<Record, ErrorCode>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.0)
+            .poll_next(cx)
+            .map(|next| next.map(|(_, record)| record))
+    }
+}
+
+impl ConsumerStream for PartitionStreams {
+    fn offset_commit(&mut self) -> ConsumerBoxFuture<'_> {
+        Box::pin(async {
+            for consumer_stream in self.0.values_mut() {
+                consumer_stream.offset_commit().await?;
+            }
+            Ok(())
+        })
+    }
+
+    fn offset_flush(&mut self) -> ConsumerBoxFuture<'_> {
+        Box::pin(async {
+            for consumer_stream in self.0.values_mut() {
+                consumer_stream.offset_flush().await?;
+            }
+            Ok(())
+        })
+    }
+}
+
+/// Tells every topic reader to lose its connection, as if the cluster went away.
+#[cfg(test)]
+pub(crate) static DISCONNECT: tokio::sync::Notify = tokio::sync::Notify::const_new();
+
+/// Fails once [`DISCONNECT`] is notified, ending the stream of the reader it is part of.
+#[cfg(test)]
+fn disconnect_stream() -> RecordStream {
+    struct Disconnect(Pin<Box<dyn Stream<Item = Result<Record, ErrorCode>> + Send>>);
+
+    impl Stream for Disconnect {
+        type Item = Result<Record, ErrorCode>;
+
+        fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+            self.0.as_mut().poll_next(cx)
+        }
+    }
+
+    impl ConsumerStream for Disconnect {
+        fn offset_commit(&mut self) -> ConsumerBoxFuture<'_> {
+            Box::pin(async { Ok(()) })
+        }
+
+        fn offset_flush(&mut self) -> ConsumerBoxFuture<'_> {
+            Box::pin(async { Ok(()) })
+        }
+    }
+
+    Box::new(Disconnect(Box::pin(futures_util::stream::once(async {
+        DISCONNECT.notified().await;
+        Err(ErrorCode::Other(String::from("disconnected")))
+    }))))
+}
+
+async fn commit(consumer_stream: &mut (dyn ConsumerStream + Send)) -> anyhow::Result<()> {
+    consumer_stream.offset_commit().await?;
+    consumer_stream.offset_flush().await?;
+    Ok(())
+}
+
+/// Hands `letter` to the sink, returning whether it was stored.
+async fn send_dead_letter(dead_letters: &Arc<dyn DeadLetterSink>, letter: DeadLetter) -> bool {
+    let (topic, offset) = (letter.topic.clone(), letter.offset);
+    if let Err(e) = dead_letters.send(letter).await {
+        error!(
+            "Error dead-lettering record {} of topic {}: {}",
+            offset, topic, e
+        );
+        return false;
+    }
+    true
+}
@@ -0,0 +1,129 @@
+use fluvio::{Fluvio, Offset, consumer::ConsumerConfigExtBuilder};
+use std::{
+    collections::HashMap,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tokio::sync::watch;
+use tokio_stream::{Stream, StreamExt};
+
+use crate::publisher::{
+    SubscriptionId, options::StartOffset, stream::EventStream, topic::fluvio::Error,
+};
+
+/// Where [`FluvioHandler::subscribe_from`](super::FluvioHandler::subscribe_from) starts
+/// replaying a topic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplayFrom {
+    Beginning,
+    Offset(i64),
+    /// The first record published at or after this time, in milliseconds since the Unix epoch.
+    Timestamp(i64),
+}
+
+impl From<ReplayFrom> for StartOffset {
+    fn from(from: ReplayFrom) -> Self {
+        match from {
+            ReplayFrom::Beginning => StartOffset::Beginning,
+            ReplayFrom::Offset(offset) => StartOffset::Absolute(offset),
+            ReplayFrom::Timestamp(timestamp) => StartOffset::Timestamp(timestamp),
+        }
+    }
+}
+
+/// Events of a replayed topic: its history first, then live events as they are published.
+/// Dropping the stream unsubscribes it.
+pub struct ReplayStream<T> {
+    events: EventStream<T>,
+    caught_up: watch::Receiver<bool>,
+}
+
+impl<T: Send + 'static> ReplayStream<T> {
+    pub(crate) fn new(events: EventStream<T>, caught_up: watch::Receiver<bool>) -> Self {
+        Self { events, caught_up }
+    }
+
+    pub fn id(&self) -> SubscriptionId {
+        self.events.id()
+    }
+
+    /// Whether every record published before the replay started has been handled.
+    pub fn is_caught_up(&self) -> bool {
+        *self.caught_up.borrow()
+    }
+
+    /// Waits until every record published before the replay started has been handled,
+    /// which only happens while the stream is being read. Also returns if the reader stopped.
+    pub async fn caught_up(&mut self) {
+        let _ = self.caught_up.wait_for(|caught_up| *caught_up).await;
+    }
+}
+
+impl<T> Stream for ReplayStream<T> {
+    type Item = T;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.events).poll_next(cx)
+    }
+}
+
+/// Tracks a reader up to the end offsets its topic had when the replay started.
+pub(crate) struct CatchUp {
+    pending: HashMap<u32, i64>,
+    caught_up: watch::Sender<bool>,
+}
+
+impl CatchUp {
+    pub(crate) fn new(end_offsets: HashMap<u32, i64>) -> (Self, watch::Receiver<bool>) {
+        let (caught_up, receiver) = watch::channel(end_offsets.is_empty());
+        let catch_up = Self {
+            pending: end_offsets,
+            caught_up,
+        };
+        (catch_up, receiver)
+    }
+
+    /// Records that the reader is done with `partition` up to `next_offset`.
+    pub(crate) fn advance(&mut self, partition: u32, next_offset: i64) {
+        if self
+            .pending
+            .get(&partition)
+            .is_some_and(|end| next_offset >= *end)
+        {
+            self.pending.remove(&partition);
+            if self.pending.is_empty() {
+                self.caught_up.send_replace(true);
+            }
+        }
+    }
+}
+
+/// Offset after the last record of each non-empty partition of `topic`, all of them when
+/// `partitions` is empty.
+pub(crate) async fn end_offsets(
+    fluvio: &Fluvio,
+    topic: &str,
+    partitions: &[u32],
+) -> anyhow::Result<HashMap<u32, i64>> {
+    let mut builder = ConsumerConfigExtBuilder::default();
+    builder
+        .topic(topic)
+        .offset_start(Offset::from_end(1))
+        .disable_continuous(true);
+    for partition in partitions {
+        builder.partition(*partition);
+    }
+    let consumer_config = builder.build().map_err(Error::ErrorCreatingConsumer)?;
+    let mut consumer_stream = fluvio
+        .consumer_with_config(consumer_config)
+        .await
+        .map_err(Error::ErrorCreatingConsumer)?;
+
+    let mut end_offsets = HashMap::new();
+    while let Some(record) = consumer_stream.next().await {
+        let record = record?;
+        let end = end_offsets.entry(record.partition()).or_insert(0);
+        *end = (*end).max(record.offset() + 1);
+    }
+    Ok(end_offsets)
+}
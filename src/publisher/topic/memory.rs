@@ -0,0 +1,163 @@
+use async_trait::async_trait;
+use std::{collections::HashMap, sync::Arc};
+use tokio::{
+    sync::{RwLock, broadcast},
+    task::JoinHandle,
+};
+use tracing::warn;
+
+use crate::publisher::{
+    EventManager, EventSubscriberHdlrFn, Subscription, SubscriptionId, TypedEvent,
+    envelope::Envelope,
+    options::SubscribeOptions,
+    schema::Versioned,
+    stream::EventStream,
+    subscribers::{ReaderKey, StopSignal, Subscribers},
+    topic::{Topic, TopicEvent},
+};
+
+type ChannelMap<T> = RwLock<HashMap<Topic, broadcast::Sender<Envelope<T>>>>;
+
+const TOPIC_CAPACITY: usize = 1024;
+
+/// In-process [`EventManager`] that routes events through one broadcast channel per topic,
+/// mirroring the topic readers of [`FluvioHandler`](super::fluvio::FluvioHandler) without a broker.
+pub struct InMemoryHandler<T: TypedEvent> {
+    subscribers: Arc<Subscribers<T>>,
+    topics: ChannelMap<T>,
+}
+
+impl<T: TypedEvent> InMemoryHandler<T> {
+    pub fn new() -> Self {
+        Self {
+            subscribers: Default::default(),
+            topics: Default::default(),
+        }
+    }
+}
+
+impl<T: TypedEvent> Default for InMemoryHandler<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl<T> EventManager for InMemoryHandler<T>
+where
+    T: TypedEvent + TopicEvent + Versioned + Clone + Send + Sync + 'static,
+    T::EventType: TopicEvent,
+{
+    type Event = T;
+
+    async fn subscribe(
+        &self,
+        event: Self::Event,
+        listener: EventSubscriberHdlrFn<Self::Event>,
+    ) -> anyhow::Result<Subscription> {
+        self.subscribe_with(event, listener, SubscribeOptions::default())
+            .await
+    }
+
+    async fn subscribe_with(
+        &self,
+        event: Self::Event,
+        listener: EventSubscriberHdlrFn<Self::Event>,
+        options: SubscribeOptions,
+    ) -> anyhow::Result<Subscription> {
+        let topic = event.event_topic();
+        self.subscribers
+            .subscribe(
+                event.event_type(),
+                topic,
+                listener,
+                options,
+                async |key, stop| Ok(self.topic_reader(key, stop).await),
+            )
+            .await
+    }
+
+    async fn subscribe_stream(
+        &self,
+        event_type: <Self::Event as TypedEvent>::EventType,
+    ) -> anyhow::Result<EventStream<Self::Event>> {
+        let topic = event_type.event_topic();
+        let (listener, events) = EventStream::channel();
+        let subscription = self
+            .subscribers
+            .subscribe(
+                event_type,
+                topic,
+                listener,
+                SubscribeOptions::default(),
+                async |key, stop| Ok(self.topic_reader(key, stop).await),
+            )
+            .await?;
+        Ok(EventStream::new(events, subscription))
+    }
+
+    async fn unsubscribe(&self, subscription: SubscriptionId) -> anyhow::Result<()> {
+        self.subscribers.unsubscribe(subscription).await;
+        Ok(())
+    }
+
+    async fn notify_envelope(&self, envelope: Envelope<Self::Event>) -> anyhow::Result<()> {
+        let lock = self.topics.read().await;
+        // Like a Fluvio reader starting at `Offset::end()`, nobody listening means nobody receives it.
+        if let Some(sender) = lock.get(envelope.event.event_topic()) {
+            let _ = sender.send(envelope);
+        }
+        Ok(())
+    }
+}
+
+impl<T> InMemoryHandler<T>
+where
+    T: TypedEvent + Clone + Send + Sync + 'static,
+{
+    async fn topic_reader(&self, key: &ReaderKey, stop: StopSignal) -> JoinHandle<()> {
+        let receiver = self
+            .topics
+            .write()
+            .await
+            .entry(key.topic)
+            .or_insert_with(|| broadcast::channel(TOPIC_CAPACITY).0)
+            .subscribe();
+        new_topic_reader(key.clone(), receiver, stop, &self.subscribers)
+    }
+}
+
+fn new_topic_reader<T>(
+    key: ReaderKey,
+    mut receiver: broadcast::Receiver<Envelope<T>>,
+    mut stop: StopSignal,
+    subscribers: &Arc<Subscribers<T>>,
+) -> JoinHandle<()>
+where
+    T: TypedEvent + Clone + Send + Sync + 'static,
+{
+    let subscribers = subscribers.clone();
+
+    tokio::spawn(async move {
+        loop {
+            let received = tokio::select! {
+                biased;
+                _ = stop.stopped() => break,
+                received = receiver.recv() => received,
+            };
+            let envelope = match received {
+                Ok(envelope) => envelope,
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    warn!("In-memory topic reader lagged, {} events skipped", skipped);
+                    continue;
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            };
+
+            let Envelope {
+                metadata, event, ..
+            } = envelope;
+            metadata.scope(subscribers.dispatch(&key, event)).await;
+        }
+    })
+}
@@ -1,7 +1,16 @@
+use crate::publisher::topic::config::TopicConfig;
+
+pub mod config;
 pub mod fluvio;
+pub mod memory;
 
 pub type Topic = &'static str;
 
 pub trait TopicEvent {
     fn event_topic(&self) -> Topic;
+
+    /// Settings the topic is created with, unless the handler was given others for it.
+    fn topic_config(&self) -> TopicConfig {
+        TopicConfig::default()
+    }
 }
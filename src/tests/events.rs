@@ -0,0 +1,89 @@
+use std::collections::HashSet;
+
+use crate::{
+    DevcordEvent,
+    events::{Event, EventType, user::UserUpdated},
+    publisher::{
+        TypedEvent,
+        topic::{TopicEvent, config::TopicConfig},
+    },
+};
+
+#[test]
+pub fn catalogue_lists_every_event_type() {
+    let event_types = EventType::all();
+    let kinds = Event::kinds();
+
+    let unique = event_types.iter().collect::<HashSet<_>>();
+    assert_eq!(event_types.len(), unique.len());
+    for kind in &kinds {
+        assert!(event_types.contains(&kind.event_type));
+        assert_eq!(kind.event_type.event_topic(), kind.topic);
+    }
+    for event_type in &event_types {
+        assert!(kinds.iter().any(|kind| kind.event_type == *event_type));
+    }
+}
+
+#[test]
+pub fn catalogue_kinds_are_unique() -> anyhow::Result<()> {
+    let kinds = Event::kinds();
+    let tags = kinds
+        .iter()
+        .map(|kind| (&kind.service, &kind.kind))
+        .collect::<HashSet<_>>();
+    assert_eq!(kinds.len(), tags.len());
+
+    for (event, kind) in Event::samples().into_iter().zip(kinds) {
+        let decoded = serde_json::from_slice::<Event>(&serde_json::to_vec(&event)?)?;
+        assert_eq!(event, decoded);
+        assert_eq!(kind.event_type, decoded.event_type());
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+enum ConfiguredEventType {
+    Configured(ConfiguredType),
+}
+
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+enum ConfiguredType {
+    Partitioned,
+    Plain,
+}
+
+#[derive(DevcordEvent)]
+#[event(event_type = ConfiguredEventType::Configured, types = ConfiguredType)]
+enum ConfiguredEvent {
+    #[event(type = Partitioned, topic = "test-partitioned")]
+    First(UserUpdated),
+    #[event(type = Partitioned, topic = "test-partitioned", config = partitioned_config)]
+    Second(UserUpdated),
+    #[event(type = Plain, topic = "test-plain")]
+    Third(UserUpdated),
+}
+
+fn partitioned_config() -> TopicConfig {
+    TopicConfig::default().with_partitions(3)
+}
+
+#[test]
+pub fn derived_events_provide_their_topic_config() {
+    assert_eq!(
+        partitioned_config(),
+        ConfiguredType::Partitioned.topic_config()
+    );
+    // The variant leaving the config out still shares the one of its type.
+    for event in [ConfiguredEvent::First, ConfiguredEvent::Second] {
+        assert_eq!(
+            partitioned_config(),
+            event(UserUpdated::default()).topic_config()
+        );
+    }
+    assert_eq!(
+        TopicConfig::default(),
+        ConfiguredEvent::Third(UserUpdated::default()).topic_config()
+    );
+}
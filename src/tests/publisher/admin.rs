@@ -0,0 +1,18 @@
+use crate::publisher::topic::fluvio::admin::in_namespace;
+
+#[test]
+pub fn namespaces_leave_out_nested_and_sibling_ones() {
+    assert!(in_namespace(Some("staging"), "staging.auth-signed"));
+    assert!(in_namespace(Some("staging"), "staging.auth-signed-dlq"));
+    assert!(!in_namespace(Some("staging"), "staging.eu.auth-signed"));
+    assert!(!in_namespace(Some("staging"), "staging-eu.auth-signed"));
+    assert!(!in_namespace(Some("staging"), "auth-signed"));
+    assert!(!in_namespace(Some("staging.eu"), "staging.auth-signed"));
+    assert!(in_namespace(Some("staging.eu"), "staging.eu.auth-signed"));
+}
+
+#[test]
+pub fn no_namespace_leaves_out_every_namespace() {
+    assert!(in_namespace(None, "auth-signed"));
+    assert!(!in_namespace(None, "staging.auth-signed"));
+}
@@ -0,0 +1,101 @@
+use std::{collections::HashMap, sync::Arc};
+
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "cbor")]
+use crate::publisher::codec::CborCodec;
+#[cfg(feature = "msgpack")]
+use crate::publisher::codec::MsgpackCodec;
+#[cfg(feature = "bincode")]
+use crate::publisher::codec::{BincodeCodec, BincodeEvent};
+use crate::{
+    events::{
+        Event,
+        user::{UserEvent, UserUpdated},
+    },
+    publisher::{
+        codec::{Codec, Codecs, JsonCodec, frame, unframe},
+        envelope::Envelope,
+        schema::Versioned,
+        topic::TopicEvent,
+    },
+};
+
+fn roundtrip<T, C>(codec: C, events: Vec<T>) -> anyhow::Result<()>
+where
+    T: Serialize + for<'a> Deserialize<'a> + Versioned + PartialEq + std::fmt::Debug + 'static,
+    C: Codec<T> + 'static,
+{
+    let codecs = Codecs::new(Arc::new(JsonCodec), HashMap::new());
+    for event in events {
+        let envelope = Envelope::new(event).with_source_service("Test");
+        let value = frame(codec.content_type(), &codec.encode(&envelope)?)?;
+        assert_eq!(envelope, codecs.decode(&value)?);
+    }
+    Ok(())
+}
+
+#[test]
+pub fn json_codec_roundtrip() -> anyhow::Result<()> {
+    roundtrip(JsonCodec, Event::samples())
+}
+
+#[cfg(feature = "msgpack")]
+#[test]
+pub fn msgpack_codec_roundtrip() -> anyhow::Result<()> {
+    roundtrip(MsgpackCodec, Event::samples())
+}
+
+#[cfg(feature = "cbor")]
+#[test]
+pub fn cbor_codec_roundtrip() -> anyhow::Result<()> {
+    roundtrip(CborCodec, Event::samples())
+}
+
+#[cfg(feature = "bincode")]
+#[test]
+pub fn bincode_codec_roundtrip() -> anyhow::Result<()> {
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    enum Plain {
+        Updated { id: String },
+    }
+
+    impl Versioned for Plain {}
+
+    impl BincodeEvent for Plain {}
+
+    let envelope = Envelope::new(Plain::Updated {
+        id: String::from("Test"),
+    });
+    let codecs = Codecs::new(Arc::new(BincodeCodec), HashMap::new());
+    let value = codecs.encode("test", &envelope)?;
+    assert_eq!(envelope, codecs.decode(&value)?);
+
+    Ok(())
+}
+
+#[test]
+pub fn codecs_keep_json_unframed() -> anyhow::Result<()> {
+    let envelope = Envelope::new(Event::UserEvent(UserEvent::UserUpdatedEvent(UserUpdated {
+        id: String::from("Test"),
+    })));
+    let codecs = Codecs::new(Arc::new(JsonCodec), HashMap::new());
+
+    let value = codecs.encode(envelope.event.event_topic(), &envelope)?;
+    assert_eq!(serde_json::to_vec(&envelope)?, value);
+    assert_eq!(envelope, codecs.decode(&value)?);
+
+    Ok(())
+}
+
+#[test]
+pub fn codecs_reject_unknown_content_types() -> anyhow::Result<()> {
+    let codecs = Codecs::<Event>::new(Arc::new(JsonCodec), HashMap::new());
+
+    let value = frame("application/unknown", b"{}")?;
+    assert_eq!(("application/unknown", &b"{}"[..]), unframe(&value)?);
+    assert!(codecs.decode(&value).is_err());
+    assert!(codecs.decode(&[0, 8, b'j']).is_err());
+
+    Ok(())
+}
@@ -0,0 +1,45 @@
+use crate::publisher::{
+    encryption::{decrypt, encrypt},
+    keys::{KeyProvider, StaticKeys},
+};
+
+const KEY: [u8; 32] = [7; 32];
+
+#[test]
+pub fn encrypted_records_decrypt() -> anyhow::Result<()> {
+    let keys = StaticKeys::new("key-1", KEY);
+
+    let encrypted = encrypt(&keys, b"private message")?;
+    assert!(
+        !encrypted
+            .windows(b"private".len())
+            .any(|window| window == b"private")
+    );
+    assert_eq!(
+        b"private message"[..],
+        *decrypt(Some(&keys as &dyn KeyProvider), &encrypted)?
+    );
+
+    Ok(())
+}
+
+#[test]
+pub fn plain_records_are_left_as_they_are() -> anyhow::Result<()> {
+    assert_eq!(b"{}"[..], *decrypt(None, b"{}")?);
+    Ok(())
+}
+
+#[test]
+pub fn encrypted_records_need_their_key() -> anyhow::Result<()> {
+    let keys = StaticKeys::new("key-1", KEY);
+    let other = StaticKeys::new("key-1", [8; 32]);
+    let rotated = StaticKeys::new("key-2", [9; 32]);
+
+    let encrypted = encrypt(&keys, b"private message")?;
+    assert!(decrypt(None, &encrypted).is_err());
+    assert!(decrypt(Some(&other as &dyn KeyProvider), &encrypted).is_err());
+    assert!(decrypt(Some(&rotated as &dyn KeyProvider), &encrypted).is_err());
+    assert!(encrypt(&StaticKeys::new("short", [7; 16]), b"{}").is_err());
+
+    Ok(())
+}
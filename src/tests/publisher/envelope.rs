@@ -0,0 +1,39 @@
+use serde_json::{from_slice, to_vec};
+
+use crate::{
+    events::{
+        Event,
+        user::{UserEvent, UserUpdated},
+    },
+    publisher::{envelope::Envelope, schema::Versioned},
+};
+
+#[test]
+pub fn envelope_roundtrip() -> anyhow::Result<()> {
+    let envelope = Envelope::new(Event::UserEvent(UserEvent::UserUpdatedEvent(UserUpdated {
+        id: String::from("Test"),
+    })))
+    .with_source_service("Test");
+
+    let decoded = Envelope::<Event>::from_slice(&to_vec(&envelope)?)?;
+    assert_eq!(envelope, decoded);
+    assert_eq!(Event::CURRENT_VERSION, decoded.metadata.schema_version);
+
+    Ok(())
+}
+
+#[test]
+pub fn envelope_accepts_bare_events() -> anyhow::Result<()> {
+    let event = Event::UserEvent(UserEvent::UserUpdatedEvent(UserUpdated {
+        id: String::from("Test"),
+    }));
+
+    let decoded = Envelope::<Event>::from_slice(&to_vec(&event)?)?;
+    assert_eq!(event, decoded.event);
+    assert_eq!(None, decoded.metadata.source_service);
+
+    let envelope: serde_json::Value = from_slice(&to_vec(&Envelope::new(event))?)?;
+    assert_eq!("user", envelope["event"]["service"]);
+
+    Ok(())
+}
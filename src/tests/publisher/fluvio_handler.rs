@@ -1,28 +1,663 @@
-use std::time::Duration;
+use std::{
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::Duration,
+};
 
+use fluvio::metadata::topic::CompressionAlgorithm;
 use serial_test::serial;
-use tokio::time::sleep;
+use tokio::{
+    sync::mpsc,
+    time::{sleep, timeout},
+};
+use tokio_stream::StreamExt;
 
+#[cfg(feature = "msgpack")]
+use crate::publisher::codec::MsgpackCodec;
 use crate::{
-    events::Event,
-    publisher::topic::fluvio::FluvioHandler,
+    events::{
+        Event, EventType,
+        auth::{AuthEvent, UserLoggedIn},
+        group::{GroupCreatedEvent, GroupEvent},
+        message::{MessageEvent, MessageEventType, MessageSent},
+        user::{UserEvent, UserUpdated},
+    },
+    publisher::{
+        EventManager, TypedEvent,
+        ack::ack,
+        dead_letter::{DeadLetter, DeadLetterReason, DeadLetterSink, InMemoryDeadLetters},
+        encryption::ENCRYPTED_MAGIC,
+        envelope::Envelope,
+        keys::StaticKeys,
+        now_millis,
+        options::{AckMode, StartOffset, SubscribeOptions},
+        retry::RetryPolicy,
+        topic::{
+            TopicEvent,
+            config::{TopicCompression, TopicConfig},
+            fluvio::{
+                FluvioHandler, FluvioHandlerBuilder,
+                dead_letter::dead_letter_topic,
+                reader::{DISCONNECT, ReaderState},
+                replay::ReplayFrom,
+            },
+        },
+    },
     tests::publisher::{
-        test_notify, test_override_subscribe, test_subscribe_only_chosen_events, test_unsubscribe,
+        subscribe_failing, subscribe_receiver, subscribe_receiver_with, test_drop_subscription,
+        test_envelope_causation, test_envelope_metadata, test_independent_consumers,
+        test_multiple_subscribers, test_notify, test_retry_failed_listener, test_retry_gives_up,
+        test_subscribe_from_listener, test_subscribe_only_chosen_events, test_subscribe_stream,
+        test_unsubscribe, test_unsubscribe_only_chosen_subscription,
     },
 };
 
 const TEST_TIMEOUT: u64 = 400;
+const TEST_NAMESPACE: &str = "test";
 
 async fn test<T: AsyncFnOnce(FluvioHandler<Event>) -> anyhow::Result<()>>(
     test: T,
+) -> anyhow::Result<()> {
+    test_with(FluvioHandler::builder(), test).await
+}
+
+async fn test_with<T: AsyncFnOnce(FluvioHandler<Event>) -> anyhow::Result<()>>(
+    builder: FluvioHandlerBuilder<Event>,
+    test: T,
 ) -> anyhow::Result<()> {
     sleep(Duration::from_millis(TEST_TIMEOUT)).await;
-    let handler: FluvioHandler<Event> = FluvioHandler::new().await.unwrap();
+    let handler = builder.namespace(TEST_NAMESPACE).build().await.unwrap();
     handler.reset_fluvio().await.unwrap();
 
     test(handler).await
 }
 
+async fn wait_dead_letters<F>(dead_letters: F) -> anyhow::Result<Vec<DeadLetter>>
+where
+    F: AsyncFn() -> anyhow::Result<Vec<DeadLetter>>,
+{
+    timeout(Duration::from_secs(10), async {
+        loop {
+            let letters = dead_letters().await?;
+            if !letters.is_empty() {
+                return Ok(letters);
+            }
+            sleep(Duration::from_millis(TEST_TIMEOUT)).await;
+        }
+    })
+    .await?
+}
+
+async fn test_dead_letter_undecodable(handler: FluvioHandler<Event>) -> anyhow::Result<()> {
+    let event = Event::UserEvent(UserEvent::UserUpdatedEvent(UserUpdated {
+        id: String::from("Test"),
+    }));
+    let topic = event.event_topic();
+
+    let (_subscription, mut rx) = subscribe_receiver(&handler, &event).await?;
+
+    handler.produce_raw(topic, b"not an event").await?;
+
+    let letters = wait_dead_letters(async || handler.dead_letters(topic).await).await?;
+    assert_eq!(DeadLetterReason::Undecodable, letters[0].reason);
+    assert_eq!(b"not an event".to_vec(), letters[0].payload);
+
+    let received_event = timeout(Duration::from_secs(1), rx.recv()).await.ok();
+    assert_eq!(None, received_event);
+
+    Ok(())
+}
+
+async fn test_inspect_without_dead_letters(handler: FluvioHandler<Event>) -> anyhow::Result<()> {
+    let topic = Event::UserEvent(UserEvent::UserUpdatedEvent(UserUpdated::default())).event_topic();
+
+    assert!(handler.dead_letters(topic).await?.is_empty());
+    assert!(handler.quarantined(topic).await?.is_empty());
+    // Inspecting leaves the topics to be created by the first letter.
+    assert!(
+        handler
+            .record_keys(&dead_letter_topic(topic))
+            .await
+            .is_err()
+    );
+
+    Ok(())
+}
+
+async fn test_start_from_beginning(handler: FluvioHandler<Event>) -> anyhow::Result<()> {
+    let event = Event::UserEvent(UserEvent::UserUpdatedEvent(UserUpdated {
+        id: String::from("Test"),
+    }));
+
+    handler.notify(event.clone()).await?;
+
+    let options = SubscribeOptions::default().with_start(StartOffset::Beginning);
+    let (_subscription, mut rx) = subscribe_receiver_with(&handler, &event, options).await?;
+
+    assert_eq!(Some(event), rx.recv().await);
+
+    Ok(())
+}
+
+async fn test_resume_from_committed_offset(handler: FluvioHandler<Event>) -> anyhow::Result<()> {
+    let event_1 = Event::UserEvent(UserEvent::UserUpdatedEvent(UserUpdated {
+        id: String::from("Test 1"),
+    }));
+
+    let event_2 = Event::UserEvent(UserEvent::UserUpdatedEvent(UserUpdated {
+        id: String::from("Test 2"),
+    }));
+
+    let options = SubscribeOptions::default()
+        .with_consumer_name("consumer-test-resume")
+        .with_ack(AckMode::AfterHandle);
+
+    let (subscription, mut rx) =
+        subscribe_receiver_with(&handler, &event_1, options.clone()).await?;
+    handler.notify(event_1.clone()).await?;
+    assert_eq!(Some(event_1), rx.recv().await);
+    subscription.unsubscribe().await;
+
+    handler.notify(event_2.clone()).await?;
+
+    let (_subscription, mut rx) = subscribe_receiver_with(&handler, &event_2, options).await?;
+    assert_eq!(Some(event_2), rx.recv().await);
+
+    Ok(())
+}
+
+async fn test_restart_from_committed_offset(
+    handler: FluvioHandler<Event>,
+    start: StartOffset,
+) -> anyhow::Result<()> {
+    let event_1 = Event::UserEvent(UserEvent::UserUpdatedEvent(UserUpdated {
+        id: String::from("Test 1"),
+    }));
+    let event_2 = Event::UserEvent(UserEvent::UserUpdatedEvent(UserUpdated {
+        id: String::from("Test 2"),
+    }));
+
+    let options = SubscribeOptions::default()
+        .with_consumer_name("consumer-test-restart")
+        .with_ack(AckMode::AfterHandle)
+        .with_start(start);
+
+    handler.notify(event_1.clone()).await?;
+    let (subscription, mut rx) =
+        subscribe_receiver_with(&handler, &event_1, options.clone()).await?;
+    assert_eq!(Some(event_1), rx.recv().await);
+    subscription.unsubscribe().await;
+
+    // The start only applies to the first run of the consumer, later ones resume.
+    handler.notify(event_2.clone()).await?;
+    let (_subscription, mut rx) = subscribe_receiver_with(&handler, &event_2, options).await?;
+    assert_eq!(Some(event_2), rx.recv().await);
+    assert!(timeout(Duration::from_secs(1), rx.recv()).await.is_err());
+
+    Ok(())
+}
+
+async fn test_manual_ack(handler: FluvioHandler<Event>) -> anyhow::Result<()> {
+    let event = Event::UserEvent(UserEvent::UserUpdatedEvent(UserUpdated {
+        id: String::from("Test"),
+    }));
+
+    let options = SubscribeOptions::default()
+        .with_consumer_name("consumer-test-manual")
+        .with_ack(AckMode::Manual);
+
+    let (subscription, mut rx) = subscribe_receiver_with(&handler, &event, options.clone()).await?;
+    handler.notify(event.clone()).await?;
+    assert_eq!(Some(event.clone()), rx.recv().await);
+    subscription.unsubscribe().await;
+
+    let (_subscription, mut rx) =
+        subscribe_receiver_with(&handler, &event, options.with_start(StartOffset::Beginning))
+            .await?;
+    assert_eq!(Some(event), rx.recv().await);
+
+    Ok(())
+}
+
+async fn test_manual_ack_redelivers(handler: FluvioHandler<Event>) -> anyhow::Result<()> {
+    let event_1 = Event::UserEvent(UserEvent::UserUpdatedEvent(UserUpdated {
+        id: String::from("Test 1"),
+    }));
+    let event_2 = Event::UserEvent(UserEvent::UserUpdatedEvent(UserUpdated {
+        id: String::from("Test 2"),
+    }));
+
+    let options = SubscribeOptions::default()
+        .with_consumer_name("consumer-test-redeliver")
+        .with_ack(AckMode::Manual);
+
+    let (tx, mut rx) = mpsc::channel(1);
+    let unacknowledged = event_1.clone();
+    let mut redelivered = false;
+    let _subscription = handler
+        .subscribe_with(
+            event_1.clone(),
+            Box::new(move |event| {
+                // The first event is only acknowledged once it is read again.
+                let acknowledge =
+                    event != unacknowledged || std::mem::replace(&mut redelivered, true);
+                let tx = tx.clone();
+                Box::pin(async move {
+                    if acknowledge {
+                        ack();
+                    }
+                    tx.send(event).await?;
+                    Ok(())
+                })
+            }),
+            options,
+        )
+        .await?;
+
+    handler.notify(event_1.clone()).await?;
+    handler.notify(event_2.clone()).await?;
+
+    assert_eq!(Some(event_1.clone()), rx.recv().await);
+    assert_eq!(Some(event_1), rx.recv().await);
+    assert_eq!(Some(event_2), rx.recv().await);
+
+    Ok(())
+}
+
+async fn test_manual_ack_dead_letters(
+    sink: Arc<InMemoryDeadLetters>,
+    handler: FluvioHandler<Event>,
+) -> anyhow::Result<()> {
+    let [failing, unacknowledged, acknowledged] = ["Failing", "Unacknowledged", "Acknowledged"]
+        .map(|id| {
+            Event::UserEvent(UserEvent::UserUpdatedEvent(UserUpdated {
+                id: String::from(id),
+            }))
+        });
+    let topic = failing.event_topic();
+
+    let options = SubscribeOptions::default()
+        .with_consumer_name("consumer-test-manual-dead-letters")
+        .with_ack(AckMode::Manual);
+
+    let (tx, mut rx) = mpsc::channel(8);
+    let (failed, ignored) = (failing.clone(), unacknowledged.clone());
+    let _subscription = handler
+        .subscribe_with(
+            failing.clone(),
+            Box::new(move |event| {
+                let (fail, acknowledge) = (event == failed, event != ignored);
+                let tx = tx.clone();
+                Box::pin(async move {
+                    tx.send(event).await?;
+                    if fail {
+                        anyhow::bail!("Failing listener");
+                    }
+                    if acknowledge {
+                        ack();
+                    }
+                    Ok(())
+                })
+            }),
+            options,
+        )
+        .await?;
+
+    handler.notify(failing.clone()).await?;
+    handler.notify(unacknowledged.clone()).await?;
+    handler.notify(acknowledged.clone()).await?;
+
+    // The failing event is dead-lettered at once, the unacknowledged one once it was read
+    // as many times as the redelivery policy allows.
+    assert_eq!(Some(failing), rx.recv().await);
+    for _ in 0..3 {
+        assert_eq!(Some(unacknowledged.clone()), rx.recv().await);
+    }
+    assert_eq!(Some(acknowledged), rx.recv().await);
+    assert!(timeout(Duration::from_secs(1), rx.recv()).await.is_err());
+
+    let letters = sink.dead_letters(&handler.topic_name(topic)).await?;
+    let reasons = letters
+        .iter()
+        .map(|letter| (letter.offset, letter.reason))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        vec![
+            (0, DeadLetterReason::ListenerFailed),
+            (1, DeadLetterReason::Unacknowledged)
+        ],
+        reasons
+    );
+
+    Ok(())
+}
+
+async fn test_dead_letter_redrive(
+    sink: Arc<InMemoryDeadLetters>,
+    handler: FluvioHandler<Event>,
+) -> anyhow::Result<()> {
+    let event = Event::UserEvent(UserEvent::UserUpdatedEvent(UserUpdated {
+        id: String::from("Test"),
+    }));
+    let topic = event.event_topic();
+
+    let (_subscription, _, mut rx) =
+        subscribe_failing(&handler, &event, 1, RetryPolicy::none()).await?;
+
+    handler
+        .notify_envelope(Envelope::new(event.clone()).with_partition_key("redrive"))
+        .await?;
+
+    let name = handler.topic_name(topic);
+    let letters = wait_dead_letters(async || sink.dead_letters(&name).await).await?;
+    assert_eq!(DeadLetterReason::ListenerFailed, letters[0].reason);
+    assert_eq!(Some(b"redrive".to_vec()), letters[0].key);
+
+    handler.redrive(&letters[0]).await?;
+    assert_eq!(Some(event), rx.recv().await);
+    assert_eq!(
+        vec![Some(b"redrive".to_vec()); 2],
+        handler.record_keys(topic).await?
+    );
+
+    Ok(())
+}
+
+async fn test_shutdown_drains_handlers(handler: FluvioHandler<Event>) -> anyhow::Result<()> {
+    let event = Event::UserEvent(UserEvent::UserUpdatedEvent(UserUpdated {
+        id: String::from("Test"),
+    }));
+
+    let (started_tx, mut started) = mpsc::channel(1);
+    let finished = Arc::new(AtomicBool::new(false));
+    let finished_listener = finished.clone();
+    let _subscription = handler
+        .subscribe(
+            event.clone(),
+            Box::new(move |_| {
+                let started_tx = started_tx.clone();
+                let finished = finished_listener.clone();
+                Box::pin(async move {
+                    started_tx.send(()).await?;
+                    sleep(Duration::from_millis(TEST_TIMEOUT)).await;
+                    finished.store(true, Ordering::SeqCst);
+                    Ok(())
+                })
+            }),
+        )
+        .await?;
+
+    handler.notify(event.clone()).await?;
+    started.recv().await;
+
+    let report = handler.shutdown(Duration::from_secs(5)).await;
+    assert!(report.is_clean());
+    assert!(finished.load(Ordering::SeqCst));
+
+    assert!(
+        handler
+            .subscribe(event.clone(), Box::new(|_| Box::pin(async { Ok(()) })))
+            .await
+            .is_err()
+    );
+    assert!(handler.notify(event).await.is_err());
+
+    Ok(())
+}
+
+async fn test_concurrent_notify(handler: FluvioHandler<Event>) -> anyhow::Result<()> {
+    let event = Event::UserEvent(UserEvent::UserUpdatedEvent(UserUpdated {
+        id: String::from("Test"),
+    }));
+
+    let (_subscription, mut rx) = subscribe_receiver(&handler, &event).await?;
+
+    let (first, second, third) = tokio::join!(
+        handler.notify(event.clone()),
+        handler.notify(event.clone()),
+        handler.notify(event.clone()),
+    );
+    first?;
+    second?;
+    third?;
+
+    for _ in 0..3 {
+        assert_eq!(Some(event.clone()), rx.recv().await);
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "msgpack")]
+async fn test_mixed_codecs(handler: FluvioHandler<Event>) -> anyhow::Result<()> {
+    let event_1 = Event::UserEvent(UserEvent::UserUpdatedEvent(UserUpdated {
+        id: String::from("Test 1"),
+    }));
+    let event_2 = Event::UserEvent(UserEvent::UserUpdatedEvent(UserUpdated {
+        id: String::from("Test 2"),
+    }));
+
+    let (_subscription, mut rx) = subscribe_receiver(&handler, &event_1).await?;
+
+    // Published by a handler still on JSON, before the topic moved to MessagePack.
+    let legacy = serde_json::to_vec(&Envelope::new(event_1.clone()))?;
+    handler.produce_raw(event_1.event_topic(), &legacy).await?;
+    handler.notify(event_2.clone()).await?;
+
+    assert_eq!(Some(event_1), rx.recv().await);
+    assert_eq!(Some(event_2), rx.recv().await);
+
+    Ok(())
+}
+
+async fn test_compressed_topic(handler: FluvioHandler<Event>) -> anyhow::Result<()> {
+    let event = Event::GroupEvent(GroupEvent::GroupCreatedEvent(GroupCreatedEvent {
+        member_ids: (0..1000).map(|id| format!("member-{id}")).collect(),
+        ..Default::default()
+    }));
+
+    let (_subscription, mut rx) = subscribe_receiver(&handler, &event).await?;
+    handler.notify(event.clone()).await?;
+
+    let spec = handler.created_spec(event.event_topic()).await?;
+    assert_eq!(&CompressionAlgorithm::Zstd, spec.get_compression_type());
+    assert_eq!(Some(event), rx.recv().await);
+
+    Ok(())
+}
+
+async fn test_signed_topic(handler: FluvioHandler<Event>) -> anyhow::Result<()> {
+    let event = Event::AuthEvent(AuthEvent::UserLoggedInEvent(UserLoggedIn {
+        id: String::from("Test"),
+        ..Default::default()
+    }));
+    let topic = event.event_topic();
+
+    let (_subscription, mut rx) = subscribe_receiver(&handler, &event).await?;
+
+    let forged = serde_json::to_vec(&Envelope::new(event.clone()))?;
+    handler.produce_raw(topic, &forged).await?;
+    let letters = wait_dead_letters(async || handler.quarantined(topic).await).await?;
+    assert_eq!(DeadLetterReason::Unverified, letters[0].reason);
+    assert_eq!(forged, letters[0].payload);
+
+    handler.notify(event.clone()).await?;
+    assert_eq!(Some(event), rx.recv().await);
+
+    Ok(())
+}
+
+async fn test_encrypted_events(handler: FluvioHandler<Event>) -> anyhow::Result<()> {
+    let event = Event::MessageEvent(MessageEvent::MessageSentEvent(MessageSent {
+        channel_id: String::from("Test"),
+        sender: String::from("Test"),
+        message: String::from("Private"),
+    }));
+
+    let (_subscription, mut rx) = subscribe_receiver(&handler, &event).await?;
+    handler.notify(event.clone()).await?;
+    assert_eq!(Some(event.clone()), rx.recv().await);
+
+    let records = handler.records(event.event_topic()).await?;
+    let value = records[0].value();
+    assert_eq!(Some(&ENCRYPTED_MAGIC), value.first());
+    assert!(
+        !value
+            .windows(b"Private".len())
+            .any(|window| window == b"Private")
+    );
+
+    Ok(())
+}
+
+async fn test_partition_keys(handler: FluvioHandler<Event>) -> anyhow::Result<()> {
+    let event = Event::MessageEvent(MessageEvent::MessageSentEvent(MessageSent {
+        channel_id: String::from("channel"),
+        sender: String::from("Test"),
+        message: String::from("Test"),
+    }));
+
+    handler.notify(event.clone()).await?;
+    handler
+        .notify_envelope(Envelope::new(event.clone()).with_partition_key("override"))
+        .await?;
+    handler.flush().await?;
+
+    let keys = handler.record_keys(event.event_topic()).await?;
+    assert_eq!(
+        vec![Some(b"channel".to_vec()), Some(b"override".to_vec())],
+        keys
+    );
+
+    Ok(())
+}
+
+async fn test_read_every_partition(handler: FluvioHandler<Event>) -> anyhow::Result<()> {
+    let events = (0..6)
+        .map(|channel| {
+            Event::MessageEvent(MessageEvent::MessageSentEvent(MessageSent {
+                channel_id: format!("channel-{channel}"),
+                sender: String::from("Test"),
+                message: String::from("Test"),
+            }))
+        })
+        .collect::<Vec<_>>();
+
+    let (_subscription, mut rx) = subscribe_receiver(&handler, &events[0]).await?;
+    for event in &events {
+        handler.notify(event.clone()).await?;
+    }
+
+    // Partitions are read side by side, so only the events of a channel keep their order.
+    let mut received = Vec::new();
+    for _ in &events {
+        received.push(rx.recv().await.unwrap());
+    }
+    assert!(events.iter().all(|event| received.contains(event)));
+
+    Ok(())
+}
+
+async fn test_replay_from_beginning(handler: FluvioHandler<Event>) -> anyhow::Result<()> {
+    let event = Event::UserEvent(UserEvent::UserUpdatedEvent(UserUpdated {
+        id: String::from("Test"),
+    }));
+
+    handler.notify(event.clone()).await?;
+    handler.notify(event.clone()).await?;
+    handler.flush().await?;
+
+    let mut replay = handler
+        .subscribe_from(event.event_type(), ReplayFrom::Beginning)
+        .await?;
+    assert_eq!(Some(event.clone()), replay.next().await);
+    assert_eq!(Some(event.clone()), replay.next().await);
+    timeout(Duration::from_secs(5), replay.caught_up()).await?;
+
+    handler.notify(event.clone()).await?;
+    assert_eq!(Some(event), replay.next().await);
+
+    Ok(())
+}
+
+async fn test_replay_from_timestamp(handler: FluvioHandler<Event>) -> anyhow::Result<()> {
+    let old_event = Event::UserEvent(UserEvent::UserUpdatedEvent(UserUpdated {
+        id: String::from("Old"),
+    }));
+    let event = Event::UserEvent(UserEvent::UserUpdatedEvent(UserUpdated {
+        id: String::from("Test"),
+    }));
+
+    handler.notify(old_event).await?;
+    handler.flush().await?;
+    sleep(Duration::from_millis(TEST_TIMEOUT)).await;
+    let timestamp = now_millis();
+    handler.notify(event.clone()).await?;
+    handler.flush().await?;
+
+    let mut replay = handler
+        .subscribe_from(event.event_type(), ReplayFrom::Timestamp(timestamp))
+        .await?;
+    assert_eq!(Some(event), replay.next().await);
+    timeout(Duration::from_secs(5), replay.caught_up()).await?;
+
+    Ok(())
+}
+
+async fn test_reader_state(
+    states: Arc<Mutex<Vec<ReaderState>>>,
+    handler: FluvioHandler<Event>,
+) -> anyhow::Result<()> {
+    let event = Event::UserEvent(UserEvent::UserUpdatedEvent(UserUpdated {
+        id: String::from("Test"),
+    }));
+
+    let (_subscription, _) = subscribe_receiver(&handler, &event).await?;
+    assert_eq!(vec![ReaderState::Connected], *states.lock().unwrap());
+
+    Ok(())
+}
+
+async fn test_reader_reconnects(
+    states: Arc<Mutex<Vec<ReaderState>>>,
+    handler: FluvioHandler<Event>,
+) -> anyhow::Result<()> {
+    let events = (1..=3)
+        .map(|id| {
+            Event::UserEvent(UserEvent::UserUpdatedEvent(UserUpdated {
+                id: format!("Test {id}"),
+            }))
+        })
+        .collect::<Vec<_>>();
+
+    let (_subscription, mut rx) = subscribe_receiver(&handler, &events[0]).await?;
+    handler.notify(events[0].clone()).await?;
+    assert_eq!(Some(events[0].clone()), rx.recv().await);
+
+    // Published while the reader waits to reconnect.
+    DISCONNECT.notify_waiters();
+    handler.notify(events[1].clone()).await?;
+    handler.notify(events[2].clone()).await?;
+    assert_eq!(Some(events[1].clone()), rx.recv().await);
+    assert_eq!(Some(events[2].clone()), rx.recv().await);
+    assert!(timeout(Duration::from_secs(1), rx.recv()).await.is_err());
+
+    let states = states.lock().unwrap().clone();
+    assert!(matches!(
+        states.as_slice(),
+        [
+            ReaderState::Connected,
+            ReaderState::Disconnected { .. },
+            ReaderState::Reconnecting { attempt: 1 },
+            ReaderState::Connected,
+        ]
+    ));
+
+    Ok(())
+}
+
 #[tokio::test]
 #[serial]
 pub async fn fluvio_test_notify() -> anyhow::Result<()> {
@@ -43,6 +678,243 @@ pub async fn fluvio_test_unsubscribe() -> anyhow::Result<()> {
 
 #[tokio::test]
 #[serial]
-pub async fn fluvio_test_override_subscribe() -> anyhow::Result<()> {
-    test(test_override_subscribe).await
+pub async fn fluvio_test_multiple_subscribers() -> anyhow::Result<()> {
+    test(test_multiple_subscribers).await
+}
+
+#[tokio::test]
+#[serial]
+pub async fn fluvio_test_unsubscribe_only_chosen_subscription() -> anyhow::Result<()> {
+    test(test_unsubscribe_only_chosen_subscription).await
+}
+
+#[tokio::test]
+#[serial]
+pub async fn fluvio_test_drop_subscription() -> anyhow::Result<()> {
+    test(test_drop_subscription).await
+}
+
+#[tokio::test]
+#[serial]
+pub async fn fluvio_test_subscribe_stream() -> anyhow::Result<()> {
+    test(test_subscribe_stream).await
+}
+
+#[tokio::test]
+#[serial]
+pub async fn fluvio_test_retry_failed_listener() -> anyhow::Result<()> {
+    test(test_retry_failed_listener).await
+}
+
+#[tokio::test]
+#[serial]
+pub async fn fluvio_test_retry_gives_up() -> anyhow::Result<()> {
+    test(test_retry_gives_up).await
+}
+
+#[tokio::test]
+#[serial]
+pub async fn fluvio_test_dead_letter_undecodable() -> anyhow::Result<()> {
+    test(test_dead_letter_undecodable).await
+}
+
+#[tokio::test]
+#[serial]
+pub async fn fluvio_test_inspect_without_dead_letters() -> anyhow::Result<()> {
+    test(test_inspect_without_dead_letters).await
+}
+
+#[tokio::test]
+#[serial]
+pub async fn fluvio_test_dead_letter_redrive() -> anyhow::Result<()> {
+    let sink = Arc::new(InMemoryDeadLetters::default());
+    let builder = FluvioHandler::builder().dead_letter_sink(sink.clone());
+    test_with(builder, async |handler| {
+        test_dead_letter_redrive(sink, handler).await
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+pub async fn fluvio_test_envelope_metadata() -> anyhow::Result<()> {
+    test(test_envelope_metadata).await
+}
+
+#[tokio::test]
+#[serial]
+pub async fn fluvio_test_envelope_causation() -> anyhow::Result<()> {
+    test(test_envelope_causation).await
+}
+
+#[tokio::test]
+#[serial]
+pub async fn fluvio_test_independent_consumers() -> anyhow::Result<()> {
+    test(test_independent_consumers).await
+}
+
+#[tokio::test]
+#[serial]
+pub async fn fluvio_test_start_from_beginning() -> anyhow::Result<()> {
+    test(test_start_from_beginning).await
+}
+
+#[tokio::test]
+#[serial]
+pub async fn fluvio_test_resume_from_committed_offset() -> anyhow::Result<()> {
+    test(test_resume_from_committed_offset).await
+}
+
+#[tokio::test]
+#[serial]
+pub async fn fluvio_test_restart_from_beginning() -> anyhow::Result<()> {
+    test(async |handler| test_restart_from_committed_offset(handler, StartOffset::Beginning).await)
+        .await
+}
+
+#[tokio::test]
+#[serial]
+pub async fn fluvio_test_restart_from_absolute_offset() -> anyhow::Result<()> {
+    test(async |handler| {
+        test_restart_from_committed_offset(handler, StartOffset::Absolute(0)).await
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+pub async fn fluvio_test_manual_ack() -> anyhow::Result<()> {
+    test(test_manual_ack).await
+}
+
+#[tokio::test]
+#[serial]
+pub async fn fluvio_test_manual_ack_redelivers() -> anyhow::Result<()> {
+    test(test_manual_ack_redelivers).await
+}
+
+#[tokio::test]
+#[serial]
+pub async fn fluvio_test_manual_ack_dead_letters() -> anyhow::Result<()> {
+    let sink = Arc::new(InMemoryDeadLetters::default());
+    let redelivery = RetryPolicy::exponential(3)
+        .with_backoff(Duration::from_millis(10), Duration::from_millis(50))
+        .with_jitter(0.0);
+    let builder = FluvioHandler::builder()
+        .dead_letter_sink(sink.clone())
+        .redelivery_policy(redelivery);
+    test_with(builder, async |handler| {
+        test_manual_ack_dead_letters(sink, handler).await
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+pub async fn fluvio_test_shutdown_drains_handlers() -> anyhow::Result<()> {
+    test(test_shutdown_drains_handlers).await
+}
+
+#[tokio::test]
+#[serial]
+pub async fn fluvio_test_subscribe_from_listener() -> anyhow::Result<()> {
+    test(test_subscribe_from_listener).await
+}
+
+#[tokio::test]
+#[serial]
+pub async fn fluvio_test_concurrent_notify() -> anyhow::Result<()> {
+    test(test_concurrent_notify).await
+}
+
+#[tokio::test]
+#[serial]
+pub async fn fluvio_test_partition_keys() -> anyhow::Result<()> {
+    test(test_partition_keys).await
+}
+
+#[tokio::test]
+#[serial]
+pub async fn fluvio_test_read_every_partition() -> anyhow::Result<()> {
+    let topic =
+        Event::MessageEvent(MessageEvent::MessageSentEvent(MessageSent::default())).event_topic();
+    let builder =
+        FluvioHandler::builder().topic_config(topic, TopicConfig::default().with_partitions(3));
+    test_with(builder, test_read_every_partition).await
+}
+
+#[tokio::test]
+#[serial]
+pub async fn fluvio_test_replay_from_beginning() -> anyhow::Result<()> {
+    test(test_replay_from_beginning).await
+}
+
+#[tokio::test]
+#[serial]
+pub async fn fluvio_test_replay_from_timestamp() -> anyhow::Result<()> {
+    test(test_replay_from_timestamp).await
+}
+
+#[cfg(feature = "msgpack")]
+#[tokio::test]
+#[serial]
+pub async fn fluvio_test_mixed_codecs() -> anyhow::Result<()> {
+    let topic = Event::UserEvent(UserEvent::UserUpdatedEvent(UserUpdated::default())).event_topic();
+    let builder = FluvioHandler::builder().topic_codec(topic, MsgpackCodec);
+    test_with(builder, test_mixed_codecs).await
+}
+
+#[tokio::test]
+#[serial]
+pub async fn fluvio_test_compressed_topic() -> anyhow::Result<()> {
+    let topic = Event::GroupEvent(GroupEvent::GroupCreatedEvent(GroupCreatedEvent::default()))
+        .event_topic();
+    let builder = FluvioHandler::builder().topic_compression(topic, TopicCompression::Zstd);
+    test_with(builder, test_compressed_topic).await
+}
+
+#[tokio::test]
+#[serial]
+pub async fn fluvio_test_signed_topic() -> anyhow::Result<()> {
+    let topic =
+        Event::AuthEvent(AuthEvent::UserLoggedInEvent(UserLoggedIn::default())).event_topic();
+    let keys = Arc::new(StaticKeys::new("test", b"secret".to_vec()));
+    let builder = FluvioHandler::builder().topic_signing(topic, keys);
+    test_with(builder, test_signed_topic).await
+}
+
+#[tokio::test]
+#[serial]
+pub async fn fluvio_test_encrypted_events() -> anyhow::Result<()> {
+    let keys = Arc::new(StaticKeys::new("test", [7; 32]));
+    let builder = FluvioHandler::builder()
+        .encryption_keys(keys)
+        .encrypt(EventType::Message(MessageEventType::Sent));
+    test_with(builder, test_encrypted_events).await
+}
+
+#[tokio::test]
+#[serial]
+pub async fn fluvio_test_reader_state() -> anyhow::Result<()> {
+    let states = Arc::new(Mutex::new(Vec::new()));
+    let recorded = states.clone();
+    let builder = FluvioHandler::builder()
+        .on_reader_state(move |_, state| recorded.lock().unwrap().push(state.clone()));
+    test_with(builder, async |handler| {
+        test_reader_state(states, handler).await
+    })
+    .await
+}
+
+#[tokio::test]
+#[serial]
+pub async fn fluvio_test_reader_reconnects() -> anyhow::Result<()> {
+    let states = Arc::new(Mutex::new(Vec::new()));
+    let recorded = states.clone();
+    let builder = FluvioHandler::builder()
+        .on_reader_state(move |_, state| recorded.lock().unwrap().push(state.clone()));
+    test_with(builder, async |handler| {
+        test_reader_reconnects(states, handler).await
+    })
+    .await
 }
@@ -0,0 +1,82 @@
+use crate::{
+    events::Event,
+    publisher::topic::memory::InMemoryHandler,
+    tests::publisher::{
+        test_drop_subscription, test_envelope_causation, test_envelope_metadata,
+        test_independent_consumers, test_multiple_subscribers, test_notify,
+        test_retry_failed_listener, test_retry_gives_up, test_subscribe_from_listener,
+        test_subscribe_only_chosen_events, test_subscribe_stream, test_unsubscribe,
+        test_unsubscribe_only_chosen_subscription,
+    },
+};
+
+async fn test<T: AsyncFnOnce(InMemoryHandler<Event>) -> anyhow::Result<()>>(
+    test: T,
+) -> anyhow::Result<()> {
+    test(InMemoryHandler::new()).await
+}
+
+#[tokio::test]
+pub async fn memory_test_notify() -> anyhow::Result<()> {
+    test(test_notify).await
+}
+
+#[tokio::test]
+pub async fn memory_test_subscribe_only_chosen_events() -> anyhow::Result<()> {
+    test(test_subscribe_only_chosen_events).await
+}
+
+#[tokio::test]
+pub async fn memory_test_unsubscribe() -> anyhow::Result<()> {
+    test(test_unsubscribe).await
+}
+
+#[tokio::test]
+pub async fn memory_test_multiple_subscribers() -> anyhow::Result<()> {
+    test(test_multiple_subscribers).await
+}
+
+#[tokio::test]
+pub async fn memory_test_unsubscribe_only_chosen_subscription() -> anyhow::Result<()> {
+    test(test_unsubscribe_only_chosen_subscription).await
+}
+
+#[tokio::test]
+pub async fn memory_test_drop_subscription() -> anyhow::Result<()> {
+    test(test_drop_subscription).await
+}
+
+#[tokio::test]
+pub async fn memory_test_subscribe_stream() -> anyhow::Result<()> {
+    test(test_subscribe_stream).await
+}
+
+#[tokio::test]
+pub async fn memory_test_retry_failed_listener() -> anyhow::Result<()> {
+    test(test_retry_failed_listener).await
+}
+
+#[tokio::test]
+pub async fn memory_test_retry_gives_up() -> anyhow::Result<()> {
+    test(test_retry_gives_up).await
+}
+
+#[tokio::test]
+pub async fn memory_test_envelope_metadata() -> anyhow::Result<()> {
+    test(test_envelope_metadata).await
+}
+
+#[tokio::test]
+pub async fn memory_test_envelope_causation() -> anyhow::Result<()> {
+    test(test_envelope_causation).await
+}
+
+#[tokio::test]
+pub async fn memory_test_independent_consumers() -> anyhow::Result<()> {
+    test(test_independent_consumers).await
+}
+
+#[tokio::test]
+pub async fn memory_test_subscribe_from_listener() -> anyhow::Result<()> {
+    test(test_subscribe_from_listener).await
+}
@@ -1,9 +1,17 @@
-use std::time::Duration;
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicU32, Ordering},
+    },
+    time::Duration,
+};
 
 use tokio::{
     sync::mpsc::{self, Receiver},
     time::timeout,
 };
+use tokio_stream::StreamExt;
+use uuid::Uuid;
 
 use crate::{
     events::{
@@ -12,30 +20,54 @@ use crate::{
         message::{MessageEvent, MessageSent},
         user::{UserEvent, UserUpdated},
     },
-    publisher::EventManager,
+    publisher::{
+        EventManager, Subscription, TypedEvent,
+        envelope::{Envelope, Metadata},
+        options::{StartOffset, SubscribeOptions},
+        retry::RetryPolicy,
+    },
 };
 
+mod admin;
+mod codec;
+mod encryption;
+mod envelope;
 mod fluvio_handler;
+mod memory_handler;
+mod retry;
+mod schema;
+mod signing;
+mod topic_config;
 
 async fn subscribe_receiver<T: EventManager<Event = Event>>(
     publisher: &T,
     event: &Event,
-) -> anyhow::Result<Receiver<Event>> {
+) -> anyhow::Result<(Subscription, Receiver<Event>)> {
+    subscribe_receiver_with(publisher, event, SubscribeOptions::default()).await
+}
+
+async fn subscribe_receiver_with<T: EventManager<Event = Event>>(
+    publisher: &T,
+    event: &Event,
+    options: SubscribeOptions,
+) -> anyhow::Result<(Subscription, Receiver<Event>)> {
     let (tx, rx) = mpsc::channel::<Event>(1);
 
-    publisher
-        .subscribe(
+    let subscription = publisher
+        .subscribe_with(
             event.clone(),
             Box::new(move |event| {
                 let tx = tx.clone();
                 Box::pin(async move {
-                    tx.send(event).await.unwrap();
+                    tx.send(event).await?;
+                    Ok(())
                 })
             }),
+            options,
         )
         .await?;
 
-    Ok(rx)
+    Ok((subscription, rx))
 }
 
 pub async fn test_notify<T: EventManager<Event = Event>>(publisher: T) -> anyhow::Result<()> {
@@ -45,7 +77,7 @@ pub async fn test_notify<T: EventManager<Event = Event>>(publisher: T) -> anyhow
         message: String::from("Test"),
     }));
 
-    let mut rx = subscribe_receiver(&publisher, &event).await?;
+    let (_subscription, mut rx) = subscribe_receiver(&publisher, &event).await?;
 
     publisher.notify(event.clone()).await?;
 
@@ -69,7 +101,7 @@ pub async fn test_subscribe_only_chosen_events<T: EventManager<Event = Event>>(
         username: String::from("Test"),
     }));
 
-    let mut rx = subscribe_receiver(&publisher, &event_1).await?;
+    let (_subscription, mut rx) = subscribe_receiver(&publisher, &event_1).await?;
 
     publisher.notify(event_1.clone()).await?;
 
@@ -91,13 +123,13 @@ pub async fn test_unsubscribe<T: EventManager<Event = Event>>(publisher: T) -> a
         id: String::from("Test"),
     }));
 
-    let mut rx = subscribe_receiver(&publisher, &event_1).await?;
+    let (subscription, mut rx) = subscribe_receiver(&publisher, &event_1).await?;
 
     publisher.notify(event_1.clone()).await?;
 
     let received_event = rx.recv().await;
-    assert_eq!(Some(event_1.clone()), received_event);
-    publisher.unsubscribe(event_1).await?;
+    assert_eq!(Some(event_1), received_event);
+    publisher.unsubscribe(subscription.detach()).await?;
     publisher.notify(event_2.clone()).await?;
     let received_event = timeout(Duration::from_secs(1), rx.recv()).await.ok();
     assert_eq!(Some(None), received_event);
@@ -105,43 +137,327 @@ pub async fn test_unsubscribe<T: EventManager<Event = Event>>(publisher: T) -> a
     Ok(())
 }
 
-pub async fn test_override_subscribe<T: EventManager<Event = Event>>(
+pub async fn test_multiple_subscribers<T: EventManager<Event = Event>>(
+    publisher: T,
+) -> anyhow::Result<()> {
+    let event = Event::UserEvent(UserEvent::UserUpdatedEvent(UserUpdated {
+        id: String::from("Test"),
+    }));
+
+    let (_subscription_1, mut rx_1) = subscribe_receiver(&publisher, &event).await?;
+    let (_subscription_2, mut rx_2) = subscribe_receiver(&publisher, &event).await?;
+
+    publisher.notify(event.clone()).await?;
+
+    assert_eq!(Some(event.clone()), rx_1.recv().await);
+    assert_eq!(Some(event), rx_2.recv().await);
+
+    Ok(())
+}
+
+pub async fn test_unsubscribe_only_chosen_subscription<T: EventManager<Event = Event>>(
+    publisher: T,
+) -> anyhow::Result<()> {
+    let event = Event::UserEvent(UserEvent::UserUpdatedEvent(UserUpdated {
+        id: String::from("Test"),
+    }));
+
+    let (subscription_1, mut rx_1) = subscribe_receiver(&publisher, &event).await?;
+    let (_subscription_2, mut rx_2) = subscribe_receiver(&publisher, &event).await?;
+
+    publisher.unsubscribe(subscription_1.detach()).await?;
+    publisher.notify(event.clone()).await?;
+
+    let received_event = timeout(Duration::from_secs(1), rx_1.recv()).await.ok();
+    assert_eq!(Some(None), received_event);
+    assert_eq!(Some(event), rx_2.recv().await);
+
+    Ok(())
+}
+
+pub async fn test_drop_subscription<T: EventManager<Event = Event>>(
+    publisher: T,
+) -> anyhow::Result<()> {
+    let event = Event::UserEvent(UserEvent::UserUpdatedEvent(UserUpdated {
+        id: String::from("Test"),
+    }));
+
+    let (subscription_1, mut rx_1) = subscribe_receiver(&publisher, &event).await?;
+    let (_subscription_2, mut rx_2) = subscribe_receiver(&publisher, &event).await?;
+
+    drop(subscription_1);
+    let received_event = timeout(Duration::from_secs(1), rx_1.recv()).await.ok();
+    assert_eq!(Some(None), received_event);
+
+    publisher.notify(event.clone()).await?;
+    assert_eq!(Some(event), rx_2.recv().await);
+
+    Ok(())
+}
+
+pub async fn test_subscribe_stream<T: EventManager<Event = Event>>(
     publisher: T,
 ) -> anyhow::Result<()> {
     let event_1 = Event::UserEvent(UserEvent::UserUpdatedEvent(UserUpdated {
         id: String::from("Test"),
     }));
 
-    let event_2 = Event::UserEvent(UserEvent::UserUpdatedEvent(UserUpdated {
+    let event_2 = Event::AuthEvent(AuthEvent::UserSignedUpEvent(UserCreated {
         id: String::from("Test"),
+        username: String::from("Test"),
     }));
 
-    let mut rx_1 = subscribe_receiver(&publisher, &event_1).await?;
+    let mut stream = publisher.subscribe_stream(event_1.event_type()).await?;
 
+    publisher.notify(event_2.clone()).await?;
     publisher.notify(event_1.clone()).await?;
 
-    let received_event = rx_1.recv().await;
-    assert_eq!(Some(event_1.clone()), received_event);
+    assert_eq!(Some(event_1), stream.next().await);
+    let received_event = timeout(Duration::from_secs(1), stream.next()).await.ok();
+    assert_eq!(None, received_event);
+
+    Ok(())
+}
+
+async fn subscribe_failing<T: EventManager<Event = Event>>(
+    publisher: &T,
+    event: &Event,
+    failures: u32,
+    retry: RetryPolicy,
+) -> anyhow::Result<(Subscription, Arc<AtomicU32>, Receiver<Event>)> {
+    let (tx, rx) = mpsc::channel::<Event>(1);
+    let attempts = Arc::new(AtomicU32::new(0));
+    let counter = attempts.clone();
+
+    let subscription = publisher
+        .subscribe_with(
+            event.clone(),
+            Box::new(move |event| {
+                let tx = tx.clone();
+                let attempt = counter.fetch_add(1, Ordering::SeqCst) + 1;
+                Box::pin(async move {
+                    if attempt <= failures {
+                        return Err(anyhow::anyhow!("Failing attempt {}", attempt));
+                    }
+                    tx.send(event).await?;
+                    Ok(())
+                })
+            }),
+            SubscribeOptions::default().with_retry(retry),
+        )
+        .await?;
+
+    Ok((subscription, attempts, rx))
+}
+
+pub async fn test_retry_failed_listener<T: EventManager<Event = Event>>(
+    publisher: T,
+) -> anyhow::Result<()> {
+    let event = Event::UserEvent(UserEvent::UserUpdatedEvent(UserUpdated {
+        id: String::from("Test"),
+    }));
+
+    let retry = RetryPolicy::exponential(3)
+        .with_backoff(Duration::from_millis(10), Duration::from_millis(50))
+        .with_jitter(0.0);
+    let (_subscription, attempts, mut rx) = subscribe_failing(&publisher, &event, 2, retry).await?;
+
+    publisher.notify(event.clone()).await?;
+
+    assert_eq!(Some(event), rx.recv().await);
+    assert_eq!(3, attempts.load(Ordering::SeqCst));
+
+    Ok(())
+}
+
+pub async fn test_retry_gives_up<T: EventManager<Event = Event>>(
+    publisher: T,
+) -> anyhow::Result<()> {
+    let event = Event::UserEvent(UserEvent::UserUpdatedEvent(UserUpdated {
+        id: String::from("Test"),
+    }));
+
+    let retry = RetryPolicy::exponential(2)
+        .with_backoff(Duration::from_millis(10), Duration::from_millis(50))
+        .with_jitter(0.0);
+    let (_subscription, attempts, mut rx) = subscribe_failing(&publisher, &event, 2, retry).await?;
+
+    publisher.notify(event.clone()).await?;
+    let received_event = timeout(Duration::from_secs(1), rx.recv()).await.ok();
+    assert_eq!(None, received_event);
+    assert_eq!(2, attempts.load(Ordering::SeqCst));
+
+    publisher.notify(event.clone()).await?;
+    assert_eq!(Some(event), rx.recv().await);
+
+    Ok(())
+}
+
+async fn subscribe_metadata<T: EventManager<Event = Event>>(
+    publisher: &T,
+    event: &Event,
+) -> anyhow::Result<(Subscription, Receiver<Option<Metadata>>)> {
+    let (tx, rx) = mpsc::channel::<Option<Metadata>>(1);
+
+    let subscription = publisher
+        .subscribe(
+            event.clone(),
+            Box::new(move |_| {
+                let tx = tx.clone();
+                Box::pin(async move {
+                    tx.send(Metadata::current()).await?;
+                    Ok(())
+                })
+            }),
+        )
+        .await?;
+
+    Ok((subscription, rx))
+}
+
+pub async fn test_envelope_metadata<T: EventManager<Event = Event>>(
+    publisher: T,
+) -> anyhow::Result<()> {
+    let event = Event::UserEvent(UserEvent::UserUpdatedEvent(UserUpdated {
+        id: String::from("Test"),
+    }));
+
+    let (_subscription, mut rx) = subscribe_metadata(&publisher, &event).await?;
+
+    let envelope = Envelope::new(event).with_correlation_id(Uuid::new_v4());
+    publisher.notify_envelope(envelope.clone()).await?;
 
-    let (tx_2, mut rx_2) = mpsc::channel::<Event>(1);
+    let Some(Some(metadata)) = rx.recv().await else {
+        return Err(anyhow::anyhow!("Metadata not available to the listener"));
+    };
+    assert_eq!(envelope.metadata.event_id, metadata.event_id);
+    assert_eq!(envelope.metadata.correlation_id, metadata.correlation_id);
+    assert_eq!(None, metadata.causation_id);
+
+    Ok(())
+}
 
-    publisher
+pub async fn test_envelope_causation<T: EventManager<Event = Event> + 'static>(
+    publisher: T,
+) -> anyhow::Result<()> {
+    let event_1 = Event::UserEvent(UserEvent::UserUpdatedEvent(UserUpdated {
+        id: String::from("Test"),
+    }));
+
+    let event_2 = Event::AuthEvent(AuthEvent::UserSignedUpEvent(UserCreated {
+        id: String::from("Test"),
+        username: String::from("Test"),
+    }));
+
+    let publisher = Arc::new(publisher);
+    let (_subscription_2, mut rx) = subscribe_metadata(publisher.as_ref(), &event_2).await?;
+
+    let forwarder = publisher.clone();
+    let _subscription_1 = publisher
         .subscribe(
             event_1.clone(),
-            Box::new(move |event| {
-                let tx = tx_2.clone();
+            Box::new(move |_| {
+                let publisher = forwarder.clone();
+                let event = event_2.clone();
+                Box::pin(async move { publisher.notify(event).await })
+            }),
+        )
+        .await?;
+
+    let envelope = Envelope::new(event_1);
+    publisher.notify_envelope(envelope.clone()).await?;
+
+    let Some(Some(metadata)) = rx.recv().await else {
+        return Err(anyhow::anyhow!("Metadata not available to the listener"));
+    };
+    assert_eq!(Some(envelope.metadata.event_id), metadata.causation_id);
+    assert_eq!(Some(envelope.metadata.event_id), metadata.correlation_id);
+
+    Ok(())
+}
+
+pub async fn test_subscribe_from_listener<T: EventManager<Event = Event> + 'static>(
+    publisher: T,
+) -> anyhow::Result<()> {
+    let event_1 = Event::UserEvent(UserEvent::UserUpdatedEvent(UserUpdated {
+        id: String::from("Test"),
+    }));
+
+    let event_2 = Event::AuthEvent(AuthEvent::UserSignedUpEvent(UserCreated {
+        id: String::from("Test"),
+        username: String::from("Test"),
+    }));
+
+    let publisher = Arc::new(publisher);
+    let (subscriptions_tx, mut subscriptions) = mpsc::channel(1);
+    let (events_tx, mut rx) = mpsc::channel(1);
+
+    let subscriber = publisher.clone();
+    let _subscription_1 = publisher
+        .subscribe(
+            event_1.clone(),
+            Box::new(move |_| {
+                let publisher = subscriber.clone();
+                let event = event_2.clone();
+                let subscriptions_tx = subscriptions_tx.clone();
+                let events_tx = events_tx.clone();
                 Box::pin(async move {
-                    tx.send(event).await.unwrap();
+                    let subscription = publisher
+                        .subscribe(
+                            event,
+                            Box::new(move |event| {
+                                let events_tx = events_tx.clone();
+                                Box::pin(async move {
+                                    events_tx.send(event).await?;
+                                    Ok(())
+                                })
+                            }),
+                        )
+                        .await?;
+                    subscriptions_tx.send(subscription).await?;
+                    Ok(())
                 })
             }),
         )
         .await?;
 
+    publisher.notify(event_1).await?;
+    let Ok(Some(_subscription_2)) = timeout(Duration::from_secs(5), subscriptions.recv()).await
+    else {
+        return Err(anyhow::anyhow!("Listener could not subscribe"));
+    };
+
+    let event_2 = Event::AuthEvent(AuthEvent::UserSignedUpEvent(UserCreated {
+        id: String::from("Test"),
+        username: String::from("Test"),
+    }));
     publisher.notify(event_2.clone()).await?;
+    assert_eq!(Some(event_2), rx.recv().await);
+
+    Ok(())
+}
+
+pub async fn test_independent_consumers<T: EventManager<Event = Event>>(
+    publisher: T,
+) -> anyhow::Result<()> {
+    let event = Event::UserEvent(UserEvent::UserUpdatedEvent(UserUpdated {
+        id: String::from("Test"),
+    }));
+
+    let options = SubscribeOptions::default()
+        .with_consumer_name("consumer-test")
+        .with_start(StartOffset::End);
+    let (_subscription_1, mut rx_1) = subscribe_receiver(&publisher, &event).await?;
+    let (_subscription_2, mut rx_2) = subscribe_receiver_with(&publisher, &event, options).await?;
+
+    publisher.notify(event.clone()).await?;
+
+    assert_eq!(Some(event.clone()), rx_1.recv().await);
+    assert_eq!(Some(event), rx_2.recv().await);
     let received_event = timeout(Duration::from_secs(1), rx_1.recv()).await.ok();
-    assert_eq!(Some(None), received_event);
-    let received_event = rx_2.recv().await;
-    assert_eq!(Some(event_2), received_event);
+    assert_eq!(None, received_event);
+    let received_event = timeout(Duration::from_secs(1), rx_2.recv()).await.ok();
+    assert_eq!(None, received_event);
 
     Ok(())
 }
@@ -0,0 +1,23 @@
+use std::time::Duration;
+
+use crate::publisher::retry::RetryPolicy;
+
+#[test]
+pub fn backoff_grows_up_to_its_max() {
+    let policy = RetryPolicy::exponential(u32::MAX).with_jitter(0.0);
+
+    assert_eq!(Duration::from_millis(100), policy.backoff(1));
+    assert_eq!(Duration::from_millis(400), policy.backoff(3));
+    assert_eq!(policy.max_backoff, policy.backoff(20));
+}
+
+#[test]
+pub fn backoff_of_late_retries_is_capped() {
+    let policy = RetryPolicy::exponential(u32::MAX).with_jitter(0.0);
+    for retry in [69, 1_000, u32::MAX] {
+        assert_eq!(policy.max_backoff, policy.backoff(retry));
+    }
+
+    let unbounded = policy.with_backoff(Duration::from_secs(1), Duration::MAX);
+    assert_eq!(Duration::MAX, unbounded.backoff(u32::MAX));
+}
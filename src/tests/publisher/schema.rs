@@ -0,0 +1,199 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+#[cfg(feature = "cbor")]
+use crate::publisher::codec::CborCodec;
+#[cfg(any(feature = "msgpack", feature = "cbor", feature = "bincode"))]
+use crate::publisher::codec::Codec;
+#[cfg(feature = "msgpack")]
+use crate::publisher::codec::MsgpackCodec;
+#[cfg(feature = "bincode")]
+use crate::publisher::codec::{BincodeCodec, BincodeEvent};
+use crate::{
+    events::Event,
+    publisher::{
+        envelope::Envelope,
+        schema::{Upcaster, Versioned},
+    },
+};
+
+/// Records of every schema version, frozen as they were published. Each holds the same
+/// events, one of each kind.
+const FIXTURES: &[(u32, &str)] = &[(1, include_str!("../fixtures/schema/v1.json"))];
+
+/// Bare events, published before envelopes existed.
+const BARE_FIXTURE: &str = include_str!("../fixtures/schema/bare.json");
+
+fn decode_fixture<T: for<'a> Deserialize<'a> + Versioned>(
+    fixture: &str,
+) -> anyhow::Result<Vec<Envelope<T>>> {
+    serde_json::from_str::<Vec<Value>>(fixture)?
+        .into_iter()
+        .map(|record| Envelope::from_slice(&serde_json::to_vec(&record)?))
+        .collect()
+}
+
+fn events(envelopes: Vec<Envelope<Event>>) -> Vec<Event> {
+    envelopes
+        .into_iter()
+        .map(|envelope| envelope.event)
+        .collect()
+}
+
+#[test]
+pub fn every_schema_version_decodes() -> anyhow::Result<()> {
+    let (version, current) = FIXTURES.last().unwrap();
+    assert_eq!(
+        Event::CURRENT_VERSION,
+        *version,
+        "freeze a fixture of the new version"
+    );
+    let current = events(decode_fixture(current)?);
+
+    for (version, fixture) in FIXTURES {
+        let envelopes = decode_fixture::<Event>(fixture)?;
+        assert!(
+            envelopes
+                .iter()
+                .all(|envelope| envelope.metadata.schema_version == *version)
+        );
+        assert_eq!(current, events(envelopes), "version {version}");
+    }
+    assert_eq!(current, events(decode_fixture(BARE_FIXTURE)?));
+
+    Ok(())
+}
+
+#[test]
+pub fn current_fixture_matches_the_events() -> anyhow::Result<()> {
+    let (_, current) = FIXTURES.last().unwrap();
+    let records = serde_json::from_str::<Vec<Value>>(current)?;
+
+    let events = events(decode_fixture(current)?);
+    for (event, record) in events.iter().zip(&records) {
+        assert_eq!(record["event"], serde_json::to_value(event)?);
+    }
+    assert_eq!(Event::kinds().len(), events.len());
+
+    Ok(())
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Greeting {
+    name: String,
+    polite: bool,
+}
+
+impl Versioned for Greeting {
+    const CURRENT_VERSION: u32 = 3;
+
+    fn upcasters() -> Vec<Upcaster> {
+        vec![
+            // Version 2 renamed `who` to `name`.
+            Upcaster::new(1, |event| {
+                let who = event
+                    .as_object_mut()
+                    .and_then(|event| event.remove("who"))
+                    .ok_or_else(|| anyhow::anyhow!("missing who"))?;
+                event["name"] = who;
+                Ok(())
+            }),
+            // Version 3 added `polite`.
+            Upcaster::new(2, |event| {
+                event["polite"] = Value::Bool(true);
+                Ok(())
+            }),
+        ]
+    }
+}
+
+fn record(version: u32, event: Value) -> Value {
+    json!({
+        "event_id": "00000000-0000-4000-8000-000000000001",
+        "occurred_at": 1700000000000i64,
+        "schema_version": version,
+        "event": event,
+    })
+}
+
+#[test]
+pub fn upcasters_chain_from_any_version() -> anyhow::Result<()> {
+    let polite = Greeting {
+        name: String::from("Ada"),
+        polite: true,
+    };
+
+    let v1 = record(1, json!({ "who": "Ada" }));
+    let v2 = record(2, json!({ "name": "Ada" }));
+    let v3 = record(3, json!({ "name": "Ada", "polite": false }));
+    let bare = json!({ "who": "Ada" });
+    for record in [v1, v2, bare] {
+        let envelope = Envelope::<Greeting>::from_slice(&serde_json::to_vec(&record)?)?;
+        assert_eq!(polite, envelope.event);
+    }
+    let envelope = Envelope::<Greeting>::from_slice(&serde_json::to_vec(&v3)?)?;
+    assert!(!envelope.event.polite);
+
+    let broken = record(1, json!({ "name": "Ada" }));
+    assert!(Envelope::<Greeting>::from_slice(&serde_json::to_vec(&broken)?).is_err());
+
+    Ok(())
+}
+
+#[test]
+pub fn current_records_are_not_upcast() -> anyhow::Result<()> {
+    let greeting = Greeting {
+        name: String::from("Ada"),
+        polite: false,
+    };
+
+    let envelope = Envelope::new(greeting);
+    assert_eq!(Greeting::CURRENT_VERSION, envelope.metadata.schema_version);
+    let decoded = Envelope::<Greeting>::from_slice(&serde_json::to_vec(&envelope)?)?;
+    assert_eq!(envelope, decoded);
+
+    Ok(())
+}
+
+#[cfg(feature = "msgpack")]
+#[test]
+pub fn msgpack_records_are_upcast() -> anyhow::Result<()> {
+    let v1 = rmp_serde::to_vec_named(&record(1, json!({ "who": "Ada" })))?;
+    let envelope = Codec::<Greeting>::decode(&MsgpackCodec, &v1)?;
+    assert_eq!("Ada", envelope.event.name);
+    assert!(envelope.event.polite);
+
+    Ok(())
+}
+
+#[cfg(feature = "cbor")]
+#[test]
+pub fn cbor_records_are_upcast() -> anyhow::Result<()> {
+    let mut v1 = Vec::new();
+    ciborium::into_writer(&record(1, json!({ "who": "Ada" })), &mut v1)?;
+    let envelope = Codec::<Greeting>::decode(&CborCodec, &v1)?;
+    assert_eq!("Ada", envelope.event.name);
+    assert!(envelope.event.polite);
+
+    Ok(())
+}
+
+#[cfg(feature = "bincode")]
+impl BincodeEvent for Greeting {}
+
+#[cfg(feature = "bincode")]
+#[test]
+pub fn bincode_rejects_other_versions() -> anyhow::Result<()> {
+    let mut envelope = Envelope::new(Greeting {
+        name: String::from("Ada"),
+        polite: false,
+    });
+    let current = BincodeCodec.encode(&envelope)?;
+    assert_eq!(envelope, BincodeCodec.decode(&current)?);
+
+    envelope.metadata.schema_version = 2;
+    let outdated = BincodeCodec.encode(&envelope)?;
+    assert!(Codec::<Greeting>::decode(&BincodeCodec, &outdated).is_err());
+
+    Ok(())
+}
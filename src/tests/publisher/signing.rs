@@ -0,0 +1,55 @@
+use crate::publisher::{
+    keys::StaticKeys,
+    signing::{SignatureError, sign, unsigned, verify},
+};
+
+#[test]
+pub fn signed_records_verify() -> anyhow::Result<()> {
+    let keys = StaticKeys::new("key-1", b"secret".to_vec());
+
+    let signed = sign(&keys, b"record")?;
+    assert_eq!(Ok(&b"record"[..]), verify(&keys, &signed));
+    assert_eq!(Ok(&b"record"[..]), unsigned(&signed));
+
+    Ok(())
+}
+
+#[test]
+pub fn signatures_survive_key_rotation() -> anyhow::Result<()> {
+    let old_keys = StaticKeys::new("key-1", b"old secret".to_vec());
+    let keys =
+        StaticKeys::new("key-2", b"new secret".to_vec()).with_key("key-1", b"old secret".to_vec());
+
+    let signed = sign(&old_keys, b"record")?;
+    assert_eq!(Ok(&b"record"[..]), verify(&keys, &signed));
+
+    let retired = StaticKeys::new("key-2", b"new secret".to_vec());
+    assert_eq!(
+        Err(SignatureError::UnknownKey(String::from("key-1"))),
+        verify(&retired, &signed)
+    );
+
+    Ok(())
+}
+
+#[test]
+pub fn forged_records_fail_verification() -> anyhow::Result<()> {
+    let keys = StaticKeys::new("key-1", b"secret".to_vec());
+    let forger = StaticKeys::new("key-1", b"guess".to_vec());
+
+    assert_eq!(Err(SignatureError::Unsigned), verify(&keys, b"record"));
+    assert_eq!(
+        Err(SignatureError::Mismatch),
+        verify(&keys, &sign(&forger, b"record")?)
+    );
+
+    let mut tampered = sign(&keys, b"record")?;
+    *tampered.last_mut().unwrap() ^= 1;
+    assert_eq!(Err(SignatureError::Mismatch), verify(&keys, &tampered));
+    assert_eq!(
+        Err(SignatureError::Malformed),
+        verify(&keys, &tampered[..8])
+    );
+
+    Ok(())
+}
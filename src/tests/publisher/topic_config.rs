@@ -0,0 +1,46 @@
+use std::time::Duration;
+
+use fluvio::Compression;
+
+use crate::publisher::topic::{
+    config::{TopicCompression, TopicConfig},
+    fluvio::{config_mismatches, producer_config, topic_spec},
+};
+
+#[test]
+pub fn topic_spec_matches_its_config() {
+    let config = TopicConfig::default()
+        .with_partitions(3)
+        .with_retention(Duration::from_secs(3600))
+        .with_max_segment_size(1024)
+        .with_compression(TopicCompression::Zstd);
+
+    let spec = topic_spec(&config);
+    assert_eq!(3, spec.replicas().partitions());
+    assert!(config_mismatches(&config, &spec).is_empty());
+}
+
+#[test]
+pub fn topic_config_mismatches() {
+    let spec = topic_spec(&TopicConfig::default());
+    let config = TopicConfig::default()
+        .with_partitions(2)
+        .with_compression(TopicCompression::Gzip);
+
+    assert_eq!(2, config_mismatches(&config, &spec).len());
+}
+
+#[test]
+pub fn producer_compresses_as_configured() -> anyhow::Result<()> {
+    let config = TopicConfig::default().with_compression(TopicCompression::Zstd);
+    assert_eq!(
+        Some(Compression::Zstd),
+        producer_config(&config)?.compression()
+    );
+    assert_eq!(
+        None,
+        producer_config(&TopicConfig::default())?.compression()
+    );
+
+    Ok(())
+}